@@ -2,6 +2,8 @@
 
 use defmt::Format;
 
+use crate::{Level, Severity};
+
 mod sealed {
     pub trait Sealed {}
 }
@@ -27,6 +29,15 @@ pub trait ErrContextConsumeDefmt<T, E: Format>: sealed::Sealed {
     fn consume_as_error(self) -> Option<T>;
     /// Consume [Err] of a [Result]. Log as "warn".
     fn consume_as_warn(self) -> Option<T>;
+
+    /// If [Err], log the error at the level of its [Severity].
+    fn log_by_severity(self) -> Result<T, E>
+    where
+        E: Severity;
+    /// Consume [Err] of a [Result]. Log at the level of its [Severity].
+    fn consume_by_severity(self) -> Option<T>
+    where
+        E: Severity;
 }
 
 /// For logging an [Option] when [None] is encountered.
@@ -102,6 +113,42 @@ impl<T, E: Format> ErrContextConsumeDefmt<T, E> for Result<T, E> {
             }
         }
     }
+
+    #[inline]
+    fn log_by_severity(self) -> Result<T, E>
+    where
+        E: Severity,
+    {
+        if let Err(err) = &self {
+            log_at_level(err.severity(), err);
+        }
+        self
+    }
+
+    #[inline]
+    fn consume_by_severity(self) -> Option<T>
+    where
+        E: Severity,
+    {
+        match self {
+            Ok(ok) => Some(ok),
+            Err(err) => {
+                log_at_level(err.severity(), &err);
+                None
+            }
+        }
+    }
+}
+
+#[inline]
+fn log_at_level(level: Level, message: impl Format) {
+    match level {
+        Level::Error => defmt::error!("{}", message),
+        Level::Warn => defmt::warn!("{}", message),
+        Level::Info => defmt::info!("{}", message),
+        Level::Debug => defmt::debug!("{}", message),
+        Level::Trace => defmt::trace!("{}", message),
+    }
 }
 
 //************************************************************************//
@@ -1,5 +1,8 @@
 #![cfg_attr(not(any(test, feature = "tracing", feature = "log")), no_std)]
 
+mod severity;
+pub use severity::*;
+
 #[cfg(any(feature = "tracing", feature = "log", feature = "stub"))]
 mod tracing_log_stub;
 #[cfg(any(feature = "tracing", feature = "log", feature = "stub"))]
@@ -0,0 +1,15 @@
+/// The level an error is logged at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+/// Implemented by errors that declare the level they should be logged at - e.g. error sets with a
+/// `#[severity(..)]` variant.
+pub trait Severity {
+    fn severity(&self) -> Level;
+}
@@ -2,6 +2,8 @@
 
 use core::fmt::Display;
 
+use crate::{Level, Severity};
+
 mod sealed {
     pub trait Sealed {}
 }
@@ -28,6 +30,15 @@ pub trait ErrContext<T, E: Display>: sealed::Sealed {
     /// Consumes the [Err] of a Result. If [Err], logging the display of the error as an "warn".
     /// Represents a bad state in which the current process can continue.
     fn consume_as_warn(self) -> Option<T>;
+
+    /// If [Err], logging the display of the error at the level of its [Severity].
+    fn log_by_severity(self) -> Result<T, E>
+    where
+        E: Severity;
+    /// Consumes the [Err] of a Result. If [Err], logging the display of the error at the level of its [Severity].
+    fn consume_by_severity(self) -> Option<T>
+    where
+        E: Severity;
 }
 
 /// For logging a [Option] when [None] is encountered.
@@ -124,6 +135,67 @@ impl<T, E: Display> ErrContext<T, E> for Result<T, E> {
             }
         }
     }
+
+    #[inline]
+    fn log_by_severity(self) -> Result<T, E>
+    where
+        E: Severity,
+    {
+        if let Err(err) = &self {
+            log_at_level(err.severity(), err);
+        }
+        self
+    }
+
+    #[inline]
+    fn consume_by_severity(self) -> Option<T>
+    where
+        E: Severity,
+    {
+        match self {
+            Ok(value) => Some(value),
+            Err(err) => {
+                log_at_level(err.severity(), &err);
+                None
+            }
+        }
+    }
+}
+
+#[inline]
+fn log_at_level(level: Level, message: impl Display) {
+    match level {
+        Level::Error => {
+            #[cfg(feature = "tracing")]
+            tracing::error!("{}", message);
+            #[cfg(feature = "log")]
+            log::error!("{}", message);
+        }
+        Level::Warn => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("{}", message);
+            #[cfg(feature = "log")]
+            log::warn!("{}", message);
+        }
+        Level::Info => {
+            #[cfg(feature = "tracing")]
+            tracing::info!("{}", message);
+            #[cfg(feature = "log")]
+            log::info!("{}", message);
+        }
+        Level::Debug => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("{}", message);
+            #[cfg(feature = "log")]
+            log::debug!("{}", message);
+        }
+        Level::Trace => {
+            #[cfg(feature = "tracing")]
+            tracing::trace!("{}", message);
+            #[cfg(feature = "log")]
+            log::trace!("{}", message);
+        }
+    }
 }
 
 //************************************************************************//
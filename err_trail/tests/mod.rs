@@ -93,6 +93,54 @@ mod tracing {
 
         assert!(logs_contain("Lazy warn context"));
     }
+
+    struct NotFound;
+
+    impl std::fmt::Display for NotFound {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "not found")
+        }
+    }
+
+    impl err_trail::Severity for NotFound {
+        fn severity(&self) -> err_trail::Level {
+            err_trail::Level::Info
+        }
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_log_by_severity() {
+        let result: Result<(), NotFound> = Err(NotFound);
+        let _ = result.log_by_severity();
+
+        logs_assert(|lines: &[&str]| {
+            match lines
+                .iter()
+                .any(|line| line.contains(" INFO ") && line.contains("not found"))
+            {
+                true => Ok(()),
+                false => Err("Expected an info log".to_string()),
+            }
+        });
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_consume_by_severity() {
+        let result: Result<(), NotFound> = Err(NotFound);
+        assert!(result.consume_by_severity().is_none());
+
+        logs_assert(|lines: &[&str]| {
+            match lines
+                .iter()
+                .any(|line| line.contains(" INFO ") && line.contains("not found"))
+            {
+                true => Ok(()),
+                false => Err("Expected an info log".to_string()),
+            }
+        });
+    }
 }
 
 #[cfg(feature = "log")]
@@ -115,7 +163,7 @@ mod log {
         fn log(&self, record: &Record) {
             if self.enabled(record.metadata()) {
                 let mut logs = self.logs.lock().unwrap();
-                logs.push(format!("{}", record.args()));
+                logs.push(format!("{} {}", record.level(), record.args()));
             }
         }
 
@@ -233,4 +281,36 @@ mod log {
 
         assert!(logs_contain("Lazy warn context"));
     }
+
+    struct NotFound(&'static str);
+
+    impl std::fmt::Display for NotFound {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{} not found", self.0)
+        }
+    }
+
+    impl err_trail::Severity for NotFound {
+        fn severity(&self) -> err_trail::Level {
+            err_trail::Level::Info
+        }
+    }
+
+    #[test]
+    fn test_log_by_severity() {
+        clear_logs();
+        let result: Result<(), NotFound> = Err(NotFound("logged"));
+        let _ = result.log_by_severity();
+
+        assert!(logs_contain("INFO logged not found"));
+    }
+
+    #[test]
+    fn test_consume_by_severity() {
+        clear_logs();
+        let result: Result<(), NotFound> = Err(NotFound("consumed"));
+        assert!(result.consume_by_severity().is_none());
+
+        assert!(logs_contain("INFO consumed not found"));
+    }
 }
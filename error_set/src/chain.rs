@@ -0,0 +1,128 @@
+use core::error::Error;
+
+/// Implemented by every error set. A variant that wraps an error displays as that error by default, so its
+/// [Error::source] is the source of the wrapped error, not the wrapped error itself. [ErrorSet::wrapped_source] returns
+/// the wrapped error, so walking the chain with [ErrorSet::chain] or a [crate::Report] does not leave it out.
+pub trait ErrorSet: Error {
+    /// The error wrapped by this variant, if any.
+    fn wrapped_source(&self) -> Option<Source<'_>>;
+
+    /// Iterates over this error, the error it wraps, and so on, then the `source()`s of the first error that is not an
+    /// error set.
+    fn chain(&self) -> Chain<'_>
+    where
+        Self: Sized + 'static,
+    {
+        Chain {
+            next: Some(Source {
+                error: self,
+                error_set: Some(self),
+                is_displayed: false,
+            }),
+        }
+    }
+
+    /// The first error of type `T` in [ErrorSet::chain].
+    fn find_source<T: Error + 'static>(&self) -> Option<&T>
+    where
+        Self: Sized + 'static,
+    {
+        self.chain().find_map(|e| e.downcast_ref::<T>())
+    }
+}
+
+/// An error in the chain of an error set, see [ErrorSet::wrapped_source].
+#[derive(Clone, Copy)]
+pub struct Source<'a> {
+    error: &'a (dyn Error + 'static),
+    error_set: Option<&'a dyn ErrorSet>,
+    is_displayed: bool,
+}
+
+impl<'a> Source<'a> {
+    pub fn error(&self) -> &'a (dyn Error + 'static) {
+        self.error
+    }
+
+    /// If the display of the error before this one in the chain already includes this error - e.g. a variant without
+    /// a `#[display(..)]`, or one with `#[display("{0}")]`.
+    pub fn is_displayed(&self) -> bool {
+        self.is_displayed
+    }
+
+    /// The next error in the chain, see [ErrorSet::chain].
+    pub fn next(&self) -> Option<Source<'a>> {
+        match self.error_set {
+            Some(error_set) => error_set.wrapped_source(),
+            None => self.error.source().map(|e| Source::new(e, false)),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn new(error: &'a (dyn Error + 'static), is_displayed: bool) -> Self {
+        Source {
+            error,
+            error_set: None,
+            is_displayed,
+        }
+    }
+}
+
+/// The first error after [error_set] in its chain that is not already displayed. Error sets return this from the
+/// deprecated [Error::cause], so a [crate::Report] of any error skips what the previous error already displays, while
+/// [Error::source] keeps returning the source of the wrapped error.
+#[doc(hidden)]
+pub fn cause<'a>(error_set: &'a dyn ErrorSet) -> Option<&'a (dyn Error + 'static)> {
+    core::iter::successors(error_set.wrapped_source(), |e| e.next())
+        .find(|e| !e.is_displayed())
+        .map(|e| e.error())
+}
+
+/// Iterator over the errors of [ErrorSet::chain].
+#[derive(Clone)]
+pub struct Chain<'a> {
+    next: Option<Source<'a>>,
+}
+
+impl<'a> Iterator for Chain<'a> {
+    type Item = &'a (dyn Error + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let source = self.next.take()?;
+        self.next = source.next();
+        Some(source.error)
+    }
+}
+
+// Creates the [Source] of a wrapped error in the generated code, which only knows the type of the wrapped error, not
+// if it is an error set. `(&SourceOf(source)).source(..)` resolves to [ErrorSetSource] if it is, since that takes
+// `self` with one less reference than [ErrorTypeSource].
+
+#[doc(hidden)]
+pub struct SourceOf<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait ErrorSetSource<'a> {
+    fn source(&self, is_displayed: bool) -> Source<'a>;
+}
+
+impl<'a, T: ErrorSet + 'static> ErrorSetSource<'a> for SourceOf<'a, T> {
+    fn source(&self, is_displayed: bool) -> Source<'a> {
+        Source {
+            error: self.0,
+            error_set: Some(self.0),
+            is_displayed,
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait ErrorTypeSource<'a> {
+    fn source(&self, is_displayed: bool) -> Source<'a>;
+}
+
+impl<'a, T: Error + 'static> ErrorTypeSource<'a> for &SourceOf<'a, T> {
+    fn source(&self, is_displayed: bool) -> Source<'a> {
+        Source::new(self.0, is_displayed)
+    }
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(not(any(test, feature = "tracing", feature = "log")), no_std)]
+#![cfg_attr(not(any(test, feature = "tracing", feature = "log", feature = "std")), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../../README.md")]
 
@@ -7,6 +7,18 @@ mod context;
 #[cfg(any(feature = "tracing", feature = "log", feature = "context_stub"))]
 pub use context::*;
 
+mod chain;
+pub use chain::{Chain, ErrorSet, Source};
+
+mod report;
+pub use report::*;
+
+mod retry;
+pub use retry::*;
+
+mod redact;
+pub use redact::*;
+
 #[cfg(feature = "defmt")]
 mod defmt_context;
 #[cfg(feature = "defmt")]
@@ -28,6 +40,7 @@ compile_error!("Features 'defmt' and 'context_stub' cannot be enabled at the sam
 pub use error_set_impl::*;
 #[cfg(any(feature = "tracing", feature = "log", feature = "context_stub", feature = "defmt"))]
 pub use err_trail::*;
+pub use err_trail::{Level, Severity};
 
 pub trait CoerceResult<T, E1> {
     fn coerce<E2: From<E1>>(self) -> Result<T, E2>;
@@ -39,3 +52,79 @@ impl<T, E1> CoerceResult<T, E1> for Result<T, E1> {
         self.map_err(Into::<E2>::into)
     }
 }
+
+/// Matches some errors of a `Result` and propagates the rest. Evaluates to the `Ok` value, or the arm of the matching
+/// error pattern. Errors that match no pattern are returned from the enclosing function, converted with `into()`,
+/// unless a final `{ error } => ..` arm handles them.
+/// See the "Handling Some Errors" section of the README.
+#[macro_export]
+macro_rules! error_match {
+    ($result:expr => { $($arms:tt)* }) => {
+        $crate::error_match!(@arms $result; []; $($arms)*)
+    };
+    (@arms $result:expr; [$($done:tt)*];) => {
+        match $result {
+            Ok(value) => value,
+            $($done)*
+            #[allow(unreachable_patterns)]
+            Err(error) => return Err(::core::convert::Into::into(error)),
+        }
+    };
+    (@arms $result:expr; [$($done:tt)*]; { $error:ident } => $fallback:expr $(,)?) => {
+        match $result {
+            Ok(value) => value,
+            $($done)*
+            Err($error) => $fallback,
+        }
+    };
+    // Like `match`, an arm with a block body does not need a trailing comma
+    (@arms $result:expr; [$($done:tt)*]; $pattern:pat $(if $guard:expr)? => $arm:block, $($rest:tt)*) => {
+        $crate::error_match!(@arms $result; [$($done)* Err($pattern) $(if $guard)? => $arm,]; $($rest)*)
+    };
+    (@arms $result:expr; [$($done:tt)*]; $pattern:pat $(if $guard:expr)? => $arm:block $($rest:tt)*) => {
+        $crate::error_match!(@arms $result; [$($done)* Err($pattern) $(if $guard)? => $arm,]; $($rest)*)
+    };
+    (@arms $result:expr; [$($done:tt)*]; $pattern:pat $(if $guard:expr)? => $arm:expr $(, $($rest:tt)*)?) => {
+        $crate::error_match!(@arms $result; [$($done)* Err($pattern) $(if $guard)? => $arm,]; $($($rest)*)?)
+    };
+}
+
+/// Used by the generated code, not public api.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "alloc")]
+    extern crate alloc;
+
+    #[cfg(feature = "alloc")]
+    pub use alloc::borrow::Cow;
+    #[cfg(feature = "alloc")]
+    pub use alloc::boxed::Box;
+    #[cfg(feature = "alloc")]
+    pub use alloc::format;
+
+    pub use crate::chain::{cause, ErrorSetSource, ErrorTypeSource, SourceOf};
+}
+
+/// Implemented for each error set that the error set `T` can be created from with `From`. Unlike `Into<T>`, this does
+/// not include other conversions, like from a source error.
+pub trait SubsetOf<T>: Into<T> {}
+
+/// Implemented for each error set with a variant of the name marked by `V`. Use [variant!] to name the marker - e.g.
+/// `E: Contains<variant!(Timeout)>`.
+pub trait Contains<V> {}
+
+/// The marker for a variant name, see [variant!]. `N` spells out the name with `Char`s, so no two names share a
+/// marker - e.g. `Variant<(Char<'I'>, Char<'o'>,)>` for `Io`.
+pub struct Variant<N>(core::marker::PhantomData<N>);
+
+/// A character of the name of a [Variant].
+#[doc(hidden)]
+pub struct Char<const C: char>;
+
+/// The marker for a variant name, for use with [Contains] - e.g. `variant!(Timeout)`.
+#[macro_export]
+macro_rules! variant {
+    ($name:ident) => {
+        $crate::__variant!($crate, $name)
+    };
+}
@@ -0,0 +1,39 @@
+use core::fmt::{self, Debug, Display, Formatter};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static REDACT: AtomicBool = AtomicBool::new(!cfg!(feature = "disable_redaction"));
+
+/// Sets if `#[redact]` fields are displayed as `<redacted>`. Enabled by default, unless the `disable_redaction`
+/// feature is enabled. Disabling is useful for local debugging.
+pub fn set_redaction(enabled: bool) {
+    REDACT.store(enabled, Ordering::Relaxed);
+}
+
+/// If `#[redact]` fields are displayed as `<redacted>`, see [set_redaction].
+pub fn is_redaction_enabled() -> bool {
+    REDACT.load(Ordering::Relaxed)
+}
+
+/// Displays and debugs as `<redacted>` while redaction is enabled, otherwise as the wrapped value. Used for
+/// `#[redact]` fields.
+pub struct Redacted<'a, T: ?Sized>(pub &'a T);
+
+impl<T: Display + ?Sized> Display for Redacted<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if is_redaction_enabled() {
+            f.write_str("<redacted>")
+        } else {
+            Display::fmt(self.0, f)
+        }
+    }
+}
+
+impl<T: Debug + ?Sized> Debug for Redacted<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if is_redaction_enabled() {
+            f.write_str("<redacted>")
+        } else {
+            Debug::fmt(self.0, f)
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use core::error::Error;
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+const BOLD_RED: &str = "\x1b[1;31m";
+const BOLD_YELLOW: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+/// Wraps an error to render it along with every error in its chain. For an error set, the chain is its
+/// [crate::ErrorSet::chain] and errors that the display of the previous error already includes are skipped - e.g. the
+/// wrapped error of a variant without a `#[display(..)]`. For other errors, the chain follows [Error::source].
+///
+/// Single-line mode (the default) renders `a: b: c`. Multi-line mode, enabled with [Report::pretty], renders
+/// ```text
+/// a
+///
+/// Caused by:
+///    0: b
+///    1: c
+/// ```
+/// [Debug] renders the same as [Display], so returning `Result<(), Report<E>>` from `main` prints the report.
+pub struct Report<E> {
+    error: E,
+    /// The error as a [dyn Error], since a boxed error does not implement [Error] itself
+    as_error: fn(&E) -> &dyn Error,
+    pretty: bool,
+    color: bool,
+    #[cfg(feature = "std")]
+    backtrace: Option<std::backtrace::Backtrace>,
+}
+
+impl<E: Error> Report<E> {
+    pub fn new(error: E) -> Self {
+        Report::with(error, |error| error)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<E: BoxedError> Report<E> {
+    /// A report of a boxed error, which does not implement [Error] itself.
+    pub fn from_boxed(error: E) -> Self {
+        Report::with(error, BoxedError::as_error)
+    }
+}
+
+/// A boxed error, see [Report::from_boxed].
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub trait BoxedError {
+    fn as_error(&self) -> &dyn Error;
+}
+
+#[cfg(feature = "alloc")]
+impl BoxedError for alloc::boxed::Box<dyn Error> {
+    fn as_error(&self) -> &dyn Error {
+        &**self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BoxedError for alloc::boxed::Box<dyn Error + Send> {
+    fn as_error(&self) -> &dyn Error {
+        &**self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BoxedError for alloc::boxed::Box<dyn Error + Send + Sync> {
+    fn as_error(&self) -> &dyn Error {
+        &**self
+    }
+}
+
+impl<E> Report<E> {
+    fn with(error: E, as_error: fn(&E) -> &dyn Error) -> Self {
+        Report {
+            error,
+            as_error,
+            pretty: false,
+            color: false,
+            #[cfg(feature = "std")]
+            backtrace: None,
+        }
+    }
+
+    /// Render each error of the chain on its own line, under "Caused by:".
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Highlight the report with ANSI colors.
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Capture a backtrace now, to render after the error chain. Like [std::backtrace::Backtrace::capture], this only
+    /// captures if enabled through the `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` environment variables.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn backtrace(mut self, backtrace: bool) -> Self {
+        self.backtrace = if backtrace {
+            Some(std::backtrace::Backtrace::capture())
+        } else {
+            None
+        };
+        self
+    }
+
+    pub fn inner(&self) -> &E {
+        &self.error
+    }
+
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+
+    fn error(&self) -> &dyn Error {
+        (self.as_error)(&self.error)
+    }
+
+    /// The errors of the chain after this one, that are not already displayed by the one before. Error sets return
+    /// the next of these from [Error::cause], other errors return their [Error::source].
+    #[allow(deprecated)]
+    fn causes(&self) -> impl Iterator<Item = &dyn Error> + '_ {
+        core::iter::successors(self.error().cause(), |&e| e.cause())
+    }
+
+    fn fmt_single_line(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_colored(f, BOLD_RED, self.error())?;
+        for error in self.causes() {
+            write!(f, ": {}", error)?;
+        }
+        Ok(())
+    }
+
+    fn fmt_multi_line(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_colored(f, BOLD_RED, self.error())?;
+        if self.causes().next().is_none() {
+            return Ok(());
+        }
+        f.write_str("\n\n")?;
+        self.fmt_colored(f, BOLD_YELLOW, "Caused by:")?;
+        let is_multiple = self.causes().nth(1).is_some();
+        for (index, error) in self.causes().enumerate() {
+            f.write_str("\n")?;
+            if is_multiple {
+                write!(f, "{:>4}: ", index)?;
+            } else {
+                f.write_str("      ")?;
+            }
+            // Indent the following lines of multi-line messages to line up with the first
+            write!(f, "{}", Indented { inner: error })?;
+        }
+        Ok(())
+    }
+
+    fn fmt_colored(&self, f: &mut Formatter<'_>, color: &str, value: impl Display) -> fmt::Result {
+        if self.color {
+            write!(f, "{}{}{}", color, value, RESET)
+        } else {
+            write!(f, "{}", value)
+        }
+    }
+}
+
+impl<E> Display for Report<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.pretty {
+            self.fmt_multi_line(f)?;
+        } else {
+            self.fmt_single_line(f)?;
+        }
+        #[cfg(feature = "std")]
+        if let Some(backtrace) = &self.backtrace {
+            if backtrace.status() == std::backtrace::BacktraceStatus::Captured {
+                f.write_str("\n\n")?;
+                self.fmt_colored(f, BOLD_YELLOW, "Stack backtrace:")?;
+                write!(f, "\n{}", backtrace)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<E> Debug for Report<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<E: Error> From<E> for Report<E> {
+    fn from(error: E) -> Self {
+        Report::new(error)
+    }
+}
+
+struct Indented<'a> {
+    inner: &'a dyn Error,
+}
+
+impl Display for Indented<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = IndentWriter { f };
+        fmt::Write::write_fmt(&mut writer, format_args!("{}", self.inner))
+    }
+}
+
+struct IndentWriter<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+}
+
+impl fmt::Write for IndentWriter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut lines = s.split('\n');
+        if let Some(first) = lines.next() {
+            self.f.write_str(first)?;
+        }
+        for line in lines {
+            self.f.write_str("\n      ")?;
+            self.f.write_str(line)?;
+        }
+        Ok(())
+    }
+}
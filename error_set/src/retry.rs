@@ -0,0 +1,121 @@
+use core::future::Future;
+use core::time::Duration;
+
+/// Implemented for error sets with a `#[retryable]` variant.
+pub trait Retryable {
+    /// If the operation that caused the error may succeed if run again.
+    fn is_retryable(&self) -> bool;
+
+    /// The delay to wait before retrying, if the error declares one. Otherwise the [Backoff] delay is used.
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// How many times to retry and how long to wait in between. The delay starts at [Backoff::initial_delay] and is
+/// multiplied by [Backoff::multiplier] after each attempt, up to [Backoff::max_delay].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    max_attempts: u32,
+    initial_delay: Duration,
+    multiplier: u32,
+    max_delay: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff::new()
+    }
+}
+
+impl Backoff {
+    /// 3 attempts, starting with a 100ms delay that doubles each time, up to 10s.
+    pub const fn new() -> Self {
+        Backoff {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(100),
+            multiplier: 2,
+            max_delay: Duration::from_secs(10),
+        }
+    }
+
+    /// The total number of times to run the operation, including the first.
+    pub const fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub const fn initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    pub const fn multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub const fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// The delay after the failed attempt [attempt], starting at 0.
+    fn delay(&self, attempt: u32, error: &impl Retryable) -> Duration {
+        if let Some(retry_after) = error.retry_after() {
+            return retry_after;
+        }
+        let multiplier = self.multiplier.saturating_pow(attempt);
+        self.initial_delay
+            .saturating_mul(multiplier)
+            .min(self.max_delay)
+    }
+}
+
+/// Runs [operation] until it succeeds, returns an error that is not retryable, or runs out of attempts. Sleeps the
+/// current thread between attempts.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn retry<T, E: Retryable>(
+    backoff: Backoff,
+    mut operation: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let mut attempt = 0;
+    loop {
+        let error = match operation() {
+            Ok(ok) => return Ok(ok),
+            Err(error) => error,
+        };
+        if !error.is_retryable() || attempt + 1 >= backoff.max_attempts {
+            return Err(error);
+        }
+        std::thread::sleep(backoff.delay(attempt, &error));
+        attempt += 1;
+    }
+}
+
+/// Like [retry], but for async operations. [sleep] waits for the given duration, so any runtime can be used - e.g.
+/// `tokio::time::sleep`.
+pub async fn retry_async<
+    T,
+    E: Retryable,
+    F: Future<Output = Result<T, E>>,
+    S: Future<Output = ()>,
+>(
+    backoff: Backoff,
+    mut operation: impl FnMut() -> F,
+    mut sleep: impl FnMut(Duration) -> S,
+) -> Result<T, E> {
+    let mut attempt = 0;
+    loop {
+        let error = match operation().await {
+            Ok(ok) => return Ok(ok),
+            Err(error) => error,
+        };
+        if !error.is_retryable() || attempt + 1 >= backoff.max_attempts {
+            return Err(error);
+        }
+        sleep(backoff.delay(attempt, &error)).await;
+        attempt += 1;
+    }
+}
@@ -365,8 +365,10 @@ pub mod value_variants1 {
         let y: Y = z.into();
         assert_eq!(y.to_string(), "This is some new display".to_string());
         let z = Z::C { val: 1 };
+        #[cfg(not(feature = "auto_display"))]
         assert_eq!(z.to_string(), "Z::C".to_string());
         let y: Y = z.into();
+        #[cfg(not(feature = "auto_display"))]
         assert_eq!(y.to_string(), "Y::C".to_string());
         let xx = XX::B {
             name: "john".to_string(),
@@ -725,13 +727,57 @@ pub mod generics {
         let auth_error: AuthError2<String> = auth_error.into();
         matches!(auth_error, AuthError2::InvalidCredentials);
 
-        let _x: X<i32> = X::A { a: 1 };
-        //let z: Z<i32> = x.into();
-        //matches!(z, Z::A { a: _ });
+        let x: X<i32> = X::A { a: 1 };
+        let z: Z<i32> = x.into();
+        assert!(matches!(z, Z::A { a: 1 }));
 
-        let _y: Y<i32> = Y::B { b: 1 };
-        //let z: Z<i32> = y.into();
-        //matches!(z, Z::B { b: _ });
+        let y: Y<i32> = Y::B { b: 1 };
+        let z: Z<i32> = y.into();
+        assert!(matches!(z, Z::B { b: 1 }));
+    }
+}
+
+#[cfg(test)]
+pub mod generics_with_different_params {
+    use std::fmt::Debug;
+
+    use error_set::error_set;
+
+    error_set! {
+        X<G: Debug> = {
+            A {
+                a: G
+            }
+        };
+        Y<H: Debug> = {
+            B {
+                b: H
+            },
+            C(std::fmt::Error),
+        };
+        Z<T: Debug> = X<T> || Y<String>;
+        W = X<Vec<u8>> || Y<u32>;
+    }
+
+    #[test]
+    fn test() {
+        let x: X<i32> = X::A { a: 1 };
+        let z: Z<i32> = x.into();
+        assert!(matches!(z, Z::A { a: 1 }));
+
+        let y: Y<String> = Y::B {
+            b: "b".to_string(),
+        };
+        let z: Z<i32> = y.into();
+        assert!(matches!(z, Z::B { b } if b == "b"));
+
+        let x: X<Vec<u8>> = X::A { a: vec![1] };
+        let w: W = x.into();
+        assert!(matches!(w, W::A { a } if a == vec![1]));
+
+        let y: Y<u32> = Y::C(std::fmt::Error);
+        let w: W = y.into();
+        assert!(matches!(w, W::C(_)));
     }
 }
 
@@ -891,36 +937,1581 @@ pub mod generics_nested {
 }
 
 #[cfg(test)]
-pub mod should_not_compile_tests {
+pub mod generics_substitution {
+    use std::fmt::Debug;
+
+    use error_set::error_set;
+
+    pub trait Store: Debug {
+        type Key: Debug;
+    }
+
+    #[derive(Debug)]
+    pub struct Memory;
+
+    impl Store for Memory {
+        type Key = u32;
+    }
+
+    /// Contains the generic name `G`, but should not be replaced
+    #[derive(Debug)]
+    pub struct GWrapper;
+
+    error_set! {
+        X<G: Store, H: Debug> = {
+            Nested {
+                nested: Vec<Option<Box<G>>>,
+            },
+            Associated {
+                key: G::Key,
+            },
+            FullyQualified {
+                key: <G as Store>::Key,
+                pair: (H, H),
+            },
+            NotGeneric {
+                wrapper: GWrapper,
+            },
+        };
+        /// Swapped names, each should only be replaced once
+        Y<H: Store, G: Debug> = X<H, G>;
+        Z = X<Memory, String>;
+    }
 
     #[test]
-    fn depends_on_self() {
-        let t = trybuild::TestCases::new();
-        t.compile_fail("tests/trybuild/depends_on_itself.rs");
+    fn test() {
+        let x: X<Memory, String> = X::Nested {
+            nested: vec![Some(Box::new(Memory))],
+        };
+        let z: Z = x.into();
+        assert!(matches!(z, Z::Nested { .. }));
+
+        let y: Y<Memory, String> = Y::Associated { key: 1 };
+        assert!(matches!(y, Y::Associated { key: 1 }));
+        let z = Z::Associated { key: 1 };
+        assert!(matches!(z, Z::Associated { key: 1 }));
+
+        let y: Y<Memory, String> = Y::FullyQualified {
+            key: 1,
+            pair: ("a".to_string(), "b".to_string()),
+        };
+        assert!(matches!(y, Y::FullyQualified { key: 1, .. }));
+        let x: X<Memory, String> = X::FullyQualified {
+            key: 1,
+            pair: ("a".to_string(), "b".to_string()),
+        };
+        let z: Z = x.into();
+        assert!(matches!(z, Z::FullyQualified { key: 1, .. }));
+
+        let z = Z::NotGeneric { wrapper: GWrapper };
+        assert!(matches!(z, Z::NotGeneric { .. }));
+    }
+}
+
+#[cfg(test)]
+pub mod maps_to {
+    use error_set::error_set;
+
+    error_set! {
+        NetworkError = {
+            TimedOut,
+            Refused {
+                port: u16,
+            },
+            Io(std::io::Error),
+        };
+        DownloadError = {
+            #[maps_to(NetworkError::TimedOut)]
+            Timeout,
+            #[maps_to(NetworkError::Refused)]
+            ConnectionRefused {
+                port: u16,
+            },
+            #[maps_to(NetworkError::Io)]
+            IoError(std::io::Error),
+        };
+        MediaError = DownloadError || {
+            TimedOut,
+        };
+        AppError = NetworkError || {
+            Shutdown,
+        };
     }
 
     #[test]
-    fn error_sources_of_diffrent_names() {
-        let t = trybuild::TestCases::new();
-        t.compile_fail("tests/trybuild/error_sources_of_diffrent_names.rs");
+    fn test() {
+        let network_error: NetworkError = DownloadError::Timeout.into();
+        assert!(matches!(network_error, NetworkError::TimedOut));
+        let network_error: NetworkError = DownloadError::ConnectionRefused { port: 80 }.into();
+        assert!(matches!(network_error, NetworkError::Refused { port: 80 }));
+        let io_error = std::io::Error::new(std::io::ErrorKind::OutOfMemory, "oops out of memory");
+        let network_error: NetworkError = DownloadError::IoError(io_error).into();
+        assert!(matches!(network_error, NetworkError::Io(_)));
+
+        // The mapping is carried through `||`
+        let network_error: NetworkError = MediaError::Timeout.into();
+        assert!(matches!(network_error, NetworkError::TimedOut));
+        // Name based conversions are unaffected
+        let media_error: MediaError = DownloadError::Timeout.into();
+        assert!(matches!(media_error, MediaError::Timeout));
+        // The mapping also applies to sets containing the mapped variant
+        let app_error: AppError = DownloadError::Timeout.into();
+        assert!(matches!(app_error, AppError::TimedOut));
+        let app_error: AppError = DownloadError::ConnectionRefused { port: 80 }.into();
+        assert!(matches!(app_error, AppError::Refused { port: 80 }));
+    }
+}
+
+#[cfg(test)]
+pub mod default_fields {
+    use error_set::error_set;
+
+    error_set! {
+        FileError = {
+            /// Fields marked `#[default]` allow `From<std::io::Error>`
+            IoError(std::io::Error) {
+                #[default]
+                path: String,
+                #[default]
+                attempts: u32,
+            },
+        };
+        ConfigError = {
+            InvalidKey {
+                key: String,
+            },
+        } || FileError;
+    }
+
+    fn read(fail: bool) -> Result<(), std::io::Error> {
+        if fail {
+            Err(std::io::Error::new(std::io::ErrorKind::NotFound, "not found"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn load() -> Result<(), ConfigError> {
+        read(true)?;
+        Ok(())
     }
 
     #[test]
-    fn floating_attributes() {
-        let t = trybuild::TestCases::new();
-        t.compile_fail("tests/trybuild/floating_attributes.rs");
+    fn test() {
+        let file_error: FileError =
+            std::io::Error::new(std::io::ErrorKind::NotFound, "not found").into();
+        assert!(matches!(
+            &file_error,
+            FileError::IoError { path, attempts: 0, .. } if path.is_empty()
+        ));
+        let file_error = file_error
+            .with_path("config.toml".to_string())
+            .with_attempts(3);
+        assert!(matches!(
+            &file_error,
+            FileError::IoError { path, attempts: 3, .. } if path == "config.toml"
+        ));
+
+        let config_error = load()
+            .map_err(|e| e.with_path("config.toml".to_string()))
+            .unwrap_err();
+        assert!(matches!(
+            &config_error,
+            ConfigError::IoError { path, .. } if path == "config.toml"
+        ));
+        let config_error = ConfigError::InvalidKey {
+            key: "key".to_string(),
+        }
+        .with_path("config.toml".to_string());
+        assert!(matches!(config_error, ConfigError::InvalidKey { .. }));
+    }
+}
+
+#[cfg(test)]
+pub mod from_source {
+    use error_set::error_set;
+
+    error_set! {
+        X = {
+            IoError1(std::io::Error),
+            #[from]
+            IoError2(std::io::Error),
+            IoError3(std::io::Error) {},
+        };
+        Y = {
+            IoError4(std::io::Error),
+        } || X;
+        #[disable(From(std::io::Error))]
+        Z = {
+            #[from]
+            IoError5(std::io::Error),
+        } || X;
+    }
+
+    impl From<std::io::Error> for Z {
+        fn from(error: std::io::Error) -> Self {
+            Z::IoError5(error)
+        }
     }
 
     #[test]
-    fn generic_specification_needed() {
-        let t = trybuild::TestCases::new();
-        t.compile_fail("tests/trybuild/generic_specification_needed.rs");
+    fn test() {
+        let io_error = std::io::Error::new(std::io::ErrorKind::OutOfMemory, "oops out of memory");
+        let x: X = io_error.into();
+        assert!(matches!(x, X::IoError2(_)));
+        let io_error = std::io::Error::new(std::io::ErrorKind::OutOfMemory, "oops out of memory");
+        let y: Y = io_error.into();
+        assert!(matches!(y, Y::IoError2(_)));
+        let io_error = std::io::Error::new(std::io::ErrorKind::OutOfMemory, "oops out of memory");
+        let z: Z = io_error.into();
+        assert!(matches!(z, Z::IoError5(_)));
+    }
+}
+
+#[cfg(test)]
+pub mod set_assertions {
+    use error_set::error_set;
+
+    error_set! {
+        #[assert_subset_of(DownloadError, MediaError)]
+        #[assert_disjoint_with(UploadError)]
+        NetworkError = {
+            TimedOut,
+            IoError(std::io::Error),
+        };
+        #[assert_subset_of(MediaError)]
+        DownloadError = NetworkError || {
+            #[maps_to(MediaError::NotFound)]
+            Missing,
+        };
+        MediaError = DownloadError || {
+            NotFound,
+        };
+        UploadError = {
+            TooLarge,
+        };
     }
 
     #[test]
-    fn no_from_for_multiple_of_same_type() {
-        let t = trybuild::TestCases::new();
-        t.compile_fail("tests/trybuild/no_from_for_multiple_of_same_type.rs");
+    fn test() {
+        let media_error: MediaError = NetworkError::TimedOut.into();
+        assert!(matches!(media_error, MediaError::TimedOut));
+        let media_error: MediaError = DownloadError::Missing.into();
+        assert!(matches!(media_error, MediaError::NotFound));
+    }
+}
+
+#[cfg(test)]
+pub mod report {
+    use error_set::{error_set, Report};
+
+    error_set! {
+        ConfigError = {
+            #[display("Could not read the config file")]
+            Io(std::io::Error),
+            Write(std::io::Error) {},
+        };
+        AppError = {
+            #[display("Could not start the app")]
+            Config(ConfigError),
+            #[display("Could not save: {0}")]
+            Save(std::io::Error),
+        };
+    }
+
+    fn app_error() -> AppError {
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
+        AppError::Config(ConfigError::Io(io_error))
+    }
+
+    #[test]
+    fn test() {
+        let report = Report::new(app_error());
+        assert_eq!(
+            report.to_string(),
+            "Could not start the app: Could not read the config file: file not found"
+        );
+        assert_eq!(format!("{:?}", report), report.to_string());
+
+        let report = Report::new(app_error()).pretty(true);
+        assert_eq!(
+            report.to_string(),
+            "Could not start the app\n\nCaused by:\n   0: Could not read the config file\n   1: file not found"
+        );
+
+        let report = Report::new(ConfigError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "file not found",
+        )))
+        .pretty(true);
+        assert_eq!(
+            report.to_string(),
+            "Could not read the config file\n\nCaused by:\n      file not found"
+        );
+
+        let report: Report<AppError> = app_error().into();
+        let report = report.color(true);
+        assert!(report
+            .to_string()
+            .starts_with("\x1b[1;31mCould not start the app\x1b[0m: "));
+        assert!(matches!(report.into_inner(), AppError::Config(_)));
+
+        // The source is not repeated if the display already includes it
+        let io_error =
+            std::io::Error::new(std::io::ErrorKind::PermissionDenied, "permission denied");
+        let report = Report::new(AppError::Save(io_error));
+        assert_eq!(report.to_string(), "Could not save: permission denied");
+
+        // Unlike a source tuple, a source struct without a display does not display as its source
+        let io_error = std::io::Error::new(std::io::ErrorKind::StorageFull, "disk full");
+        let report = Report::new(ConfigError::Write { source: io_error });
+        #[cfg(not(feature = "auto_display"))]
+        assert_eq!(report.to_string(), "ConfigError::Write: disk full");
+    }
+
+    #[derive(Debug)]
+    struct StartupError {
+        source: AppError,
+    }
+
+    impl std::fmt::Display for StartupError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Startup failed")
+        }
+    }
+
+    impl std::error::Error for StartupError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.source)
+        }
+    }
+
+    #[test]
+    fn other_errors() {
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
+        let report = Report::new(io_error);
+        assert_eq!(report.to_string(), "file not found");
+
+        // The chain of an error that is not an error set follows its `source()`, into the sets it wraps
+        let report: Report<StartupError> = StartupError {
+            source: app_error(),
+        }
+        .into();
+        assert_eq!(
+            report.to_string(),
+            "Startup failed: Could not start the app: Could not read the config file: file not found"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn boxed_errors() {
+        let error: Box<dyn std::error::Error + Send + Sync> = Box::new(app_error());
+        let report = Report::from_boxed(error).pretty(true);
+        assert_eq!(
+            report.to_string(),
+            "Could not start the app\n\nCaused by:\n   0: Could not read the config file\n   1: file not found"
+        );
+        let error: Box<dyn std::error::Error> = "invalid config".into();
+        assert_eq!(Report::from_boxed(error).to_string(), "invalid config");
+    }
+}
+
+#[cfg(test)]
+pub mod chain {
+    use error_set::{error_set, ErrorSet};
+
+    error_set! {
+        DownloadError = {
+            IoError(std::io::Error),
+            #[display("Request failed")]
+            Request(std::io::Error) {
+                url: String,
+            },
+            InvalidUrl,
+        };
+        MediaError = {
+            #[display("Could not download the media")]
+            Download(DownloadError),
+        };
+    }
+
+    error_set! {
+        UploadError = {
+            #[display("Could not upload the media")]
+            Media(MediaError),
+        };
+    }
+
+    #[test]
+    fn test() {
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "not found");
+        let media_error = MediaError::Download(DownloadError::IoError(io_error));
+        assert_eq!(media_error.chain().count(), 3);
+        let messages = media_error
+            .chain()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec!["Could not download the media", "not found", "not found"]
+        );
+        let io_error = media_error.find_source::<std::io::Error>();
+        assert!(io_error.is_some_and(|e| e.kind() == std::io::ErrorKind::NotFound));
+        assert!(media_error.find_source::<DownloadError>().is_some());
+        assert!(media_error.find_source::<std::fmt::Error>().is_none());
+
+        // The wrapped error is found even though `source()` skips it
+        let io_error = std::io::Error::new(std::io::ErrorKind::TimedOut, "timed out");
+        let download_error = DownloadError::IoError(io_error);
+        assert!(std::error::Error::source(&download_error).is_none());
+        let io_error = download_error.find_source::<std::io::Error>();
+        assert!(io_error.is_some_and(|e| e.kind() == std::io::ErrorKind::TimedOut));
+
+        let download_error = DownloadError::Request {
+            source: std::io::Error::new(std::io::ErrorKind::TimedOut, "timed out"),
+            url: "https://example.com".to_string(),
+        };
+        assert_eq!(download_error.chain().count(), 2);
+        assert!(download_error.find_source::<std::io::Error>().is_some());
+
+        assert_eq!(DownloadError::InvalidUrl.chain().count(), 1);
+        assert!(DownloadError::InvalidUrl
+            .find_source::<std::io::Error>()
+            .is_none());
+
+        // Error sets of other invocations are walked through too
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "not found");
+        let upload_error =
+            UploadError::Media(MediaError::Download(DownloadError::IoError(io_error)));
+        assert_eq!(upload_error.chain().count(), 4);
+        assert!(upload_error.find_source::<std::io::Error>().is_some());
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+pub mod try_from_dyn {
+    use error_set::error_set;
+
+    error_set! {
+        DownloadError = {
+            InvalidUrl,
+            IoError(std::io::Error),
+        };
+        MediaError = DownloadError || {
+            #[display("Could not format")]
+            Format(std::fmt::Error),
+            UnsupportedFormat,
+        };
+    }
+
+    type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+    #[test]
+    fn test() {
+        let error: BoxError = Box::new(MediaError::UnsupportedFormat);
+        let media_error = MediaError::try_from_dyn(error).unwrap();
+        assert!(matches!(media_error, MediaError::UnsupportedFormat));
+
+        // A subset
+        let error: BoxError = Box::new(DownloadError::InvalidUrl);
+        let media_error = MediaError::try_from_dyn(error).unwrap();
+        assert!(matches!(media_error, MediaError::InvalidUrl));
+
+        // A source
+        let error: BoxError = Box::new(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+        let media_error = MediaError::try_from_dyn(error).unwrap();
+        assert!(matches!(media_error, MediaError::IoError(_)));
+        let error: BoxError = Box::new(std::fmt::Error);
+        let media_error = MediaError::try_from_dyn(error).unwrap();
+        assert!(matches!(media_error, MediaError::Format(_)));
+
+        // Not a subset
+        let error: BoxError = Box::new(MediaError::UnsupportedFormat);
+        let error = DownloadError::try_from_dyn(error).unwrap_err();
+        assert!(error.downcast_ref::<MediaError>().is_some());
+    }
+}
+
+#[cfg(test)]
+pub mod marker_traits {
+    use error_set::{error_set, variant, Contains, SubsetOf};
+
+    error_set! {
+        DownloadError = {
+            Timeout,
+            IoError(std::io::Error),
+        };
+        MediaError = DownloadError || {
+            UnsupportedFormat,
+        };
+        UploadError<T: core::fmt::Debug + core::fmt::Display> = {
+            Timeout,
+            TooLarge {
+                size: T,
+            },
+        };
+    }
+
+    fn into_media_error<E: SubsetOf<MediaError>>(error: E) -> MediaError {
+        error.into()
+    }
+
+    fn has_timeout<E: Contains<variant!(Timeout)>>(_error: &E) -> bool {
+        true
+    }
+
+    #[test]
+    fn test() {
+        let media_error = into_media_error(DownloadError::Timeout);
+        assert!(matches!(media_error, MediaError::Timeout));
+        assert!(has_timeout(&DownloadError::Timeout));
+        assert!(has_timeout(&MediaError::UnsupportedFormat));
+        assert!(has_timeout(&UploadError::<u64>::Timeout));
+    }
+}
+
+#[cfg(test)]
+pub mod crate_path {
+    // A crate that re-exports `error_set`, as its users would depend on
+    mod facade {
+        pub use ::error_set;
+    }
+
+    use self::facade::error_set::{error_set, errors, variant, Contains, ErrorSet, SubsetOf};
+
+    error_set! {
+        crate = self::facade::error_set;
+        DownloadError = {
+            #[display("Download timed out")]
+            Timeout,
+            #[retryable]
+            IoError(std::io::Error),
+        };
+        MediaError = DownloadError || {
+            UnsupportedFormat,
+        };
+    }
+
+    #[errors(crate = self::facade::error_set, DownloadError || { Empty })]
+    fn fetch(fail: bool) -> Result<Vec<u8>, _> {
+        if fail {
+            Err(FetchError::Empty)
+        } else {
+            Ok(vec![1])
+        }
+    }
+
+    fn into_media_error<E: SubsetOf<MediaError>>(error: E) -> MediaError {
+        error.into()
+    }
+
+    fn has_timeout<E: Contains<variant!(Timeout)>>(_error: &E) -> bool {
+        true
+    }
+
+    #[test]
+    fn test() {
+        let media_error = into_media_error(DownloadError::Timeout);
+        assert_eq!(media_error.to_string(), "Download timed out");
+        assert!(has_timeout(&media_error));
+        assert!(media_error.wrapped_source().is_none());
+        assert!(matches!(fetch(true), Err(FetchError::Empty)));
+        assert!(has_timeout(&FetchError::Timeout));
+    }
+}
+
+#[cfg(test)]
+pub mod standalone {
+    use error_set::{error_set, ErrorSet};
+
+    error_set! {
+        #[display_prefix("Parse error: ")]
+        ParseError = {
+            #[standalone]
+            #[display("Missing field '{field}'")]
+            MissingField {
+                field: String,
+            },
+            #[standalone]
+            Empty,
+            #[standalone]
+            #[display("Could not read the input")]
+            ReadFailed(std::io::Error),
+        };
+        ConfigError = ParseError || {
+            NotFound,
+        };
+    }
+
+    fn require(field: &str, value: Option<u32>) -> Result<u32, MissingField> {
+        value.ok_or_else(|| MissingField {
+            field: field.to_string(),
+        })
+    }
+
+    fn parse(value: Option<u32>) -> Result<u32, ParseError> {
+        Ok(require("port", value)?)
+    }
+
+    fn load(value: Option<u32>) -> Result<u32, ConfigError> {
+        Ok(require("port", value)?)
+    }
+
+    #[test]
+    fn test() {
+        assert_eq!(parse(Some(1)).unwrap(), 1);
+        let parse_error = parse(None).unwrap_err();
+        assert!(matches!(parse_error, ParseError::MissingField { ref field } if field == "port"));
+        assert_eq!(parse_error.to_string(), "Parse error: Missing field 'port'");
+        let config_error = load(None).unwrap_err();
+        assert!(matches!(config_error, ConfigError::MissingField { ref field } if field == "port"));
+        assert_eq!(config_error.to_string(), "Parse error: Missing field 'port'");
+        assert_eq!(
+            MissingField {
+                field: "port".to_string()
+            }
+            .to_string(),
+            "Missing field 'port'"
+        );
+        assert!(matches!(ConfigError::from(Empty), ConfigError::Empty));
+        // The struct displays as the variant alone, without the prefix or name of a set
+        #[cfg(not(feature = "auto_display"))]
+        assert_eq!(Empty.to_string(), "Empty");
+
+        let read_failed = ReadFailed(std::io::Error::new(std::io::ErrorKind::Other, "oh no"));
+        assert!(read_failed.find_source::<std::io::Error>().is_some());
+        let config_error: ConfigError = read_failed.into();
+        assert!(matches!(config_error, ConfigError::ReadFailed(_)));
+    }
+}
+
+#[cfg(test)]
+pub mod errors_attribute {
+    use error_set::{error_set, errors};
+
+    use self::storage::StorageError;
+
+    error_set! {
+        DownloadError = {
+            #[display("Download timed out")]
+            Timeout,
+        };
+        ParseError = {
+            InvalidHeader,
+        };
+    }
+
+    mod storage {
+        use error_set::error_set;
+
+        error_set! {
+            StorageError = {
+                #[display("Could not read the file")]
+                ReadFailed(std::io::Error),
+            };
+        }
+    }
+
+    fn download(fail: bool) -> Result<Vec<u8>, DownloadError> {
+        if fail {
+            Err(DownloadError::Timeout)
+        } else {
+            Ok(vec![1])
+        }
+    }
+
+    fn parse(bytes: Vec<u8>) -> Result<u8, ParseError> {
+        bytes.first().copied().ok_or(ParseError::InvalidHeader)
+    }
+
+    #[errors(DownloadError || ParseError || {
+        #[display("The file is empty")]
+        Empty,
+        IoError(std::io::Error),
+    })]
+    fn download_and_parse(fail: bool) -> Result<u8, _> {
+        let header = parse(download(fail)?)?;
+        if header == 0 {
+            return Err(DownloadAndParseError::Empty);
+        }
+        Ok(header)
+    }
+
+    #[errors(StorageError || DownloadError)]
+    fn download_to_disk(fail: bool) -> Result<(), _> {
+        download(fail)?;
+        Err(std::io::Error::new(std::io::ErrorKind::Other, "disk full"))?
+    }
+
+    #[test]
+    fn test() {
+        assert_eq!(download_and_parse(false).unwrap(), 1);
+        let error = download_and_parse(true).unwrap_err();
+        assert!(matches!(error, DownloadAndParseError::Timeout));
+        assert_eq!(error.to_string(), "Download timed out");
+        assert_eq!(
+            DownloadAndParseError::Empty.to_string(),
+            "The file is empty"
+        );
+        let error: DownloadAndParseError = ParseError::InvalidHeader.into();
+        assert!(matches!(error, DownloadAndParseError::InvalidHeader));
+        let error: DownloadAndParseError =
+            std::io::Error::new(std::io::ErrorKind::Other, "").into();
+        assert!(matches!(error, DownloadAndParseError::IoError(_)));
+    }
+
+    #[test]
+    fn referenced_set_of_other_module() {
+        let error = download_to_disk(true).unwrap_err();
+        assert!(matches!(error, DownloadToDiskError::Timeout));
+        let error = download_to_disk(false).unwrap_err();
+        assert!(matches!(error, DownloadToDiskError::ReadFailed(_)));
+        assert_eq!(error.to_string(), "Could not read the file");
+        let error: DownloadToDiskError =
+            StorageError::ReadFailed(std::io::Error::new(std::io::ErrorKind::Other, "")).into();
+        assert!(matches!(error, DownloadToDiskError::ReadFailed(_)));
+    }
+}
+
+#[cfg(test)]
+pub mod error_match {
+    use error_set::{error_match, error_set};
+
+    error_set! {
+        DownloadError = {
+            InvalidUrl,
+            Timeout,
+        };
+        MediaError = DownloadError || {
+            UnsupportedFormat,
+        };
+    }
+
+    fn download(error: Option<DownloadError>) -> Result<u32, DownloadError> {
+        match error {
+            Some(error) => Err(error),
+            None => Ok(1),
+        }
+    }
+
+    fn load(error: Option<DownloadError>) -> Result<u32, MediaError> {
+        let value = error_match!(download(error) => {
+            DownloadError::InvalidUrl => 2,
+        });
+        Ok(value)
+    }
+
+    fn load_with_fallback(error: Option<DownloadError>, attempts: u32) -> Result<u32, MediaError> {
+        let value = error_match!(download(error) => {
+            DownloadError::Timeout if attempts > 0 => load_with_fallback(None, attempts - 1)? + 10,
+            { error } => return Err(MediaError::from(error)),
+        });
+        Ok(value)
+    }
+
+    fn load_with_blocks(error: Option<DownloadError>, attempts: u32) -> Result<u32, MediaError> {
+        let value = error_match!(download(error) => {
+            DownloadError::InvalidUrl => {
+                2
+            }
+            DownloadError::Timeout if attempts > 0 => {
+                3
+            }
+            { error } => {
+                return Err(MediaError::from(error));
+            }
+        });
+        Ok(value)
+    }
+
+    #[test]
+    fn test() {
+        assert_eq!(load_with_blocks(Some(DownloadError::InvalidUrl), 0).unwrap(), 2);
+        assert_eq!(load_with_blocks(Some(DownloadError::Timeout), 1).unwrap(), 3);
+        assert!(matches!(
+            load_with_blocks(Some(DownloadError::Timeout), 0),
+            Err(MediaError::Timeout)
+        ));
+        assert_eq!(load(None).unwrap(), 1);
+        assert_eq!(load(Some(DownloadError::InvalidUrl)).unwrap(), 2);
+        assert!(matches!(
+            load(Some(DownloadError::Timeout)),
+            Err(MediaError::Timeout)
+        ));
+        assert_eq!(
+            load_with_fallback(Some(DownloadError::Timeout), 1).unwrap(),
+            11
+        );
+        assert!(matches!(
+            load_with_fallback(Some(DownloadError::Timeout), 0),
+            Err(MediaError::Timeout)
+        ));
+        assert!(matches!(
+            load_with_fallback(Some(DownloadError::InvalidUrl), 1),
+            Err(MediaError::InvalidUrl)
+        ));
+    }
+}
+
+#[cfg(test)]
+pub mod retryable {
+    use core::time::Duration;
+    use std::cell::Cell;
+
+    use error_set::{error_set, retry_async, Backoff};
+
+    error_set! {
+        DownloadError = {
+            #[retryable]
+            Timeout,
+            #[retryable(after_ms = 0)]
+            RateLimited {
+                limit: u32,
+            },
+            InvalidUrl,
+        };
+        MediaError = DownloadError || {
+            UnsupportedFormat,
+        };
+    }
+
+    fn block_on<F: core::future::Future>(future: F) -> F::Output {
+        use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+        fn raw_waker() -> RawWaker {
+            RawWaker::new(core::ptr::null(), &VTABLE)
+        }
+        static VTABLE: RawWakerVTable =
+            RawWakerVTable::new(|_| raw_waker(), |_| {}, |_| {}, |_| {});
+        let waker = unsafe { Waker::from_raw(raw_waker()) };
+        let mut context = Context::from_waker(&waker);
+        let mut future = core::pin::pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn test() {
+        assert!(DownloadError::Timeout.is_retryable());
+        assert!(!DownloadError::InvalidUrl.is_retryable());
+        assert!(MediaError::Timeout.is_retryable());
+        assert!(!MediaError::UnsupportedFormat.is_retryable());
+        assert_eq!(DownloadError::Timeout.retry_after(), None);
+        assert_eq!(
+            MediaError::RateLimited { limit: 1 }.retry_after(),
+            Some(Duration::ZERO)
+        );
+
+        let backoff = Backoff::new().max_attempts(3).initial_delay(Duration::ZERO);
+        let attempts = Cell::new(0);
+        let sleeps = Cell::new(0);
+        let result = block_on(retry_async(
+            backoff,
+            || {
+                attempts.set(attempts.get() + 1);
+                let attempt = attempts.get();
+                async move {
+                    if attempt < 2 {
+                        Err(DownloadError::RateLimited { limit: 1 })
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            },
+            |_| {
+                sleeps.set(sleeps.get() + 1);
+                async {}
+            },
+        ));
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(sleeps.get(), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_retry() {
+        use error_set::retry;
+
+        let backoff = Backoff::new().max_attempts(3).initial_delay(Duration::ZERO);
+        let attempts = Cell::new(0);
+        let result = retry(backoff, || {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() < 3 {
+                Err(MediaError::Timeout)
+            } else {
+                Ok(attempts.get())
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        attempts.set(0);
+        let result: Result<(), _> = retry(backoff, || {
+            attempts.set(attempts.get() + 1);
+            Err(DownloadError::Timeout)
+        });
+        assert!(matches!(result, Err(DownloadError::Timeout)));
+        assert_eq!(attempts.get(), 3);
+
+        attempts.set(0);
+        let result: Result<(), _> = retry(backoff, || {
+            attempts.set(attempts.get() + 1);
+            Err(DownloadError::InvalidUrl)
+        });
+        assert!(matches!(result, Err(DownloadError::InvalidUrl)));
+        assert_eq!(attempts.get(), 1);
+    }
+}
+
+#[cfg(test)]
+pub mod severity {
+    use error_set::{error_set, Level, Severity};
+
+    error_set! {
+        LookupError = {
+            #[severity(info)]
+            NotFound,
+            #[severity(warn)]
+            Stale {
+                age: u32,
+            },
+            Corrupted,
+        };
+        CacheError = LookupError || {
+            #[severity(debug)]
+            Evicted,
+        };
+    }
+
+    fn severity_of(error: &impl Severity) -> Level {
+        error.severity()
+    }
+
+    #[test]
+    fn test() {
+        assert_eq!(LookupError::NotFound.severity(), Level::Info);
+        assert_eq!(LookupError::Stale { age: 1 }.severity(), Level::Warn);
+        assert_eq!(LookupError::Corrupted.severity(), Level::Error);
+        assert_eq!(CacheError::NotFound.severity(), Level::Info);
+        assert_eq!(severity_of(&CacheError::Evicted), Level::Debug);
+        assert_eq!(severity_of(&CacheError::Corrupted), Level::Error);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+pub mod user_message {
+    use error_set::error_set;
+
+    error_set! {
+        #[user_message("Something went wrong with your upload")]
+        UploadError = {
+            #[display("Upload of {size} bytes exceeded the limit of {limit}")]
+            #[user_message("The file is too large, the limit is {limit} bytes")]
+            TooLarge {
+                size: u64,
+                limit: u64,
+            },
+            #[display("Connection to the storage backend failed")]
+            #[user_message("Please try again later")]
+            StorageUnavailable,
+            #[display("Database failure")]
+            Database(std::io::Error),
+        };
+        ApiError = UploadError || {
+            #[user_message("Please log in")]
+            Unauthorized,
+            NotFound,
+        };
+        #[user_message("The service is unavailable")]
+        ServiceError = UploadError || {
+            Maintenance,
+        };
+    }
+
+    #[test]
+    fn test() {
+        let too_large = UploadError::TooLarge {
+            size: 20,
+            limit: 10,
+        };
+        assert_eq!(
+            too_large.to_string(),
+            "Upload of 20 bytes exceeded the limit of 10"
+        );
+        assert_eq!(
+            too_large.user_message(),
+            "The file is too large, the limit is 10 bytes"
+        );
+        assert!(matches!(
+            UploadError::StorageUnavailable.user_message(),
+            std::borrow::Cow::Borrowed("Please try again later")
+        ));
+        let database = UploadError::Database(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "connection refused",
+        ));
+        assert_eq!(
+            database.user_message(),
+            "Something went wrong with your upload"
+        );
+
+        assert_eq!(
+            ApiError::StorageUnavailable.user_message(),
+            "Please try again later"
+        );
+        assert_eq!(ApiError::Unauthorized.user_message(), "Please log in");
+        assert_eq!(
+            ApiError::NotFound.user_message(),
+            "An unexpected error occurred."
+        );
+
+        // Variants keep the default of the set that declared them
+        let database = ServiceError::Database(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "connection refused",
+        ));
+        assert_eq!(
+            database.user_message(),
+            "Something went wrong with your upload"
+        );
+        assert_eq!(
+            ServiceError::Maintenance.user_message(),
+            "The service is unavailable"
+        );
+        assert_eq!(
+            ServiceError::StorageUnavailable.user_message(),
+            "Please try again later"
+        );
+    }
+}
+
+#[cfg(test)]
+pub mod redact {
+    use error_set::{error_set, set_redaction};
+
+    error_set! {
+        AuthError = {
+            #[display("User with email {email} does not exist")]
+            UserDoesNotExist {
+                #[redact]
+                email: String,
+            },
+            #[display("Wrong password for {}: {}", user_id, password)]
+            WrongPassword {
+                user_id: u32,
+                #[redact]
+                password: String,
+            },
+            #[display("Password of {} characters is too weak", password.len())]
+            WeakPassword {
+                #[redact]
+                password: String,
+            },
+            #[standalone]
+            #[display("Token {token:?} expired")]
+            TokenExpired {
+                #[redact]
+                token: String,
+            },
+            Locked,
+        };
+        ApiError = AuthError || {
+            Timeout(std::io::Error),
+        };
+    }
+
+    #[cfg(feature = "alloc")]
+    error_set! {
+        SignUpError = {
+            #[display("Email {email} is taken")]
+            #[user_message("An account for {email} already exists")]
+            EmailTaken {
+                #[redact]
+                email: String,
+            },
+        };
+    }
+
+    #[test]
+    fn test() {
+        let user_does_not_exist = ApiError::UserDoesNotExist {
+            email: "jane@example.com".to_string(),
+        };
+        assert_eq!(
+            user_does_not_exist.to_string(),
+            "User with email <redacted> does not exist"
+        );
+        assert_eq!(
+            format!("{:?}", user_does_not_exist),
+            "UserDoesNotExist { email: <redacted> }"
+        );
+        let wrong_password = AuthError::WrongPassword {
+            user_id: 1,
+            password: "hunter2".to_string(),
+        };
+        assert_eq!(
+            wrong_password.to_string(),
+            "Wrong password for 1: <redacted>"
+        );
+        assert_eq!(
+            format!("{:?}", wrong_password),
+            "WrongPassword { user_id: 1, password: <redacted> }"
+        );
+        // Only the field itself is redacted, not expressions using it
+        let weak_password = AuthError::WeakPassword {
+            password: "hunter2".to_string(),
+        };
+        assert_eq!(
+            weak_password.to_string(),
+            "Password of 7 characters is too weak"
+        );
+        assert_eq!(format!("{:?}", AuthError::Locked), "Locked");
+        let token_expired = TokenExpired {
+            token: "abc".to_string(),
+        };
+        assert_eq!(token_expired.to_string(), "Token <redacted> expired");
+        assert_eq!(
+            format!("{:?}", token_expired),
+            "TokenExpired { token: <redacted> }"
+        );
+
+        set_redaction(false);
+        assert_eq!(
+            user_does_not_exist.to_string(),
+            "User with email jane@example.com does not exist"
+        );
+        assert_eq!(
+            format!("{:?}", user_does_not_exist),
+            "UserDoesNotExist { email: \"jane@example.com\" }"
+        );
+        assert_eq!(token_expired.to_string(), "Token \"abc\" expired");
+        set_redaction(true);
+
+        #[cfg(feature = "alloc")]
+        {
+            let email_taken = SignUpError::EmailTaken {
+                email: "jane@example.com".to_string(),
+            };
+            assert_eq!(email_taken.to_string(), "Email <redacted> is taken");
+            assert_eq!(
+                email_taken.user_message(),
+                "An account for <redacted> already exists"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod debug_mode {
+    use error_set::error_set;
+
+    error_set! {
+        #[debug(display)]
+        UploadError = {
+            #[display("Upload of {size} bytes exceeded the limit of {limit}")]
+            TooLarge {
+                size: u64,
+                limit: u64,
+            },
+            #[display("Database failure")]
+            Database(std::io::Error),
+            Cancelled,
+        };
+        #[debug(compact)]
+        DownloadError = {
+            Timeout {
+                after_secs: u64,
+            },
+            Io(std::io::Error),
+            Parse(std::num::ParseIntError) {
+                line: usize,
+            },
+            Cancelled,
+        };
+    }
+
+    #[test]
+    fn test() {
+        let too_large = UploadError::TooLarge {
+            size: 20,
+            limit: 10,
+        };
+        assert_eq!(
+            format!("{:?}", too_large),
+            "TooLarge: Upload of 20 bytes exceeded the limit of 10"
+        );
+        let database = UploadError::Database(std::io::Error::other("disk full"));
+        assert_eq!(format!("{:?}", database), "Database: Database failure");
+        #[cfg(not(feature = "auto_display"))]
+        assert_eq!(
+            format!("{:?}", UploadError::Cancelled),
+            "Cancelled: UploadError::Cancelled"
+        );
+
+        let timeout = DownloadError::Timeout { after_secs: 3 };
+        assert_eq!(format!("{:?}", timeout), "Timeout { after_secs: 3 }");
+        let io = DownloadError::Io(std::io::Error::other("disk full"));
+        assert_eq!(format!("{:?}", io), "Io(..)");
+        let parse = DownloadError::Parse {
+            source: "x".parse::<u8>().unwrap_err(),
+            line: 2,
+        };
+        assert_eq!(format!("{:?}", parse), "Parse { line: 2, .. }");
+        assert_eq!(format!("{:?}", DownloadError::Cancelled), "Cancelled");
+    }
+}
+
+#[cfg(test)]
+pub mod auto_display {
+    use error_set::error_set;
+
+    error_set! {
+        #[display(auto)]
+        BookError = {
+            MissingBookDescription,
+            HTTPTimeout,
+            MissingField {
+                field: String,
+            },
+            OutOfRange {
+                value: i32,
+                #[redact]
+                max: i32,
+            },
+            #[display("The book is already checked out")]
+            CheckedOut,
+            Io(std::io::Error),
+            Parse(std::num::ParseIntError) {
+                line: usize,
+            },
+            Corrupted {
+                bytes: Vec<u8>,
+            },
+        };
+        PlainError = {
+            MissingBookDescription,
+        };
+    }
+
+    #[test]
+    fn test() {
+        assert_eq!(
+            BookError::MissingBookDescription.to_string(),
+            "missing book description"
+        );
+        assert_eq!(BookError::HTTPTimeout.to_string(), "http timeout");
+        let missing_field = BookError::MissingField {
+            field: "author".to_string(),
+        };
+        assert_eq!(
+            missing_field.to_string(),
+            "missing field (field = \"author\")"
+        );
+        let out_of_range = BookError::OutOfRange { value: 5, max: 3 };
+        assert_eq!(
+            out_of_range.to_string(),
+            "out of range (value = 5, max = <redacted>)"
+        );
+        assert_eq!(
+            BookError::CheckedOut.to_string(),
+            "The book is already checked out"
+        );
+        let io = BookError::Io(std::io::Error::other("disk full"));
+        assert_eq!(io.to_string(), "disk full");
+        let parse = BookError::Parse {
+            source: "x".parse::<u8>().unwrap_err(),
+            line: 2,
+        };
+        assert_eq!(parse.to_string(), "parse (line = 2)");
+        // Fields are formatted with `Debug`, so they do not need to implement `Display`
+        let corrupted = BookError::Corrupted { bytes: vec![1, 2] };
+        assert_eq!(corrupted.to_string(), "corrupted (bytes = [1, 2])");
+        #[cfg(not(feature = "auto_display"))]
+        assert_eq!(
+            PlainError::MissingBookDescription.to_string(),
+            "PlainError::MissingBookDescription"
+        );
+        #[cfg(feature = "auto_display")]
+        assert_eq!(
+            PlainError::MissingBookDescription.to_string(),
+            "missing book description"
+        );
+    }
+}
+
+#[cfg(test)]
+pub mod display_template {
+    use error_set::error_set;
+
+    error_set! {
+        #[display_prefix("storage: ")]
+        StorageError = {
+            #[display("Disk full")]
+            DiskFull,
+            Io(std::io::Error),
+            #[display("File {path} not found")]
+            NotFound {
+                path: String,
+            },
+        };
+        #[display_template("[{variant}] {message}")]
+        NetworkError = {
+            #[display("Timed out")]
+            Timeout,
+        };
+        ServiceError = StorageError || NetworkError || {
+            #[display("Service unavailable")]
+            Unavailable,
+        };
+        #[display_prefix("api: ")]
+        ApiError = StorageError || {
+            #[display("Bad request")]
+            BadRequest,
+        };
+        #[display_prefix("app: ", keep)]
+        AppError = StorageError || {
+            #[display("Crashed")]
+            Crashed,
+        };
+    }
+
+    #[test]
+    fn test() {
+        assert_eq!(StorageError::DiskFull.to_string(), "storage: Disk full");
+        let io = StorageError::Io(std::io::Error::other("broken pipe"));
+        assert_eq!(io.to_string(), "storage: broken pipe");
+        let not_found = StorageError::NotFound {
+            path: "a.txt".to_string(),
+        };
+        assert_eq!(not_found.to_string(), "storage: File a.txt not found");
+        assert_eq!(NetworkError::Timeout.to_string(), "[Timeout] Timed out");
+
+        let service: ServiceError = StorageError::DiskFull.into();
+        assert_eq!(service.to_string(), "storage: Disk full");
+        let service: ServiceError = NetworkError::Timeout.into();
+        assert_eq!(service.to_string(), "[Timeout] Timed out");
+        assert_eq!(ServiceError::Unavailable.to_string(), "Service unavailable");
+
+        let api: ApiError = StorageError::DiskFull.into();
+        assert_eq!(api.to_string(), "api: Disk full");
+        assert_eq!(ApiError::BadRequest.to_string(), "api: Bad request");
+
+        let app: AppError = StorageError::DiskFull.into();
+        assert_eq!(app.to_string(), "storage: Disk full");
+        assert_eq!(AppError::Crashed.to_string(), "app: Crashed");
+    }
+}
+
+#[cfg(test)]
+pub mod doc_display {
+    use error_set::error_set;
+
+    error_set! {
+        #[display(doc)]
+        AuthError = {
+            /// User `{name}` does not exist
+            ///
+            /// More details, which are not displayed.
+            UserDoesNotExist {
+                name: String,
+            },
+            /// The credentials are invalid
+            #[display("Invalid credentials")]
+            InvalidCredentials,
+            NoDoc,
+            /// Io failure: {0}
+            Io(std::io::Error),
+        };
+        LoginError = {
+            /// Too many attempts
+            #[display(doc)]
+            TooManyAttempts,
+            /// Not displayed
+            Locked,
+        } || AuthError;
+    }
+
+    #[test]
+    fn test() {
+        let user_does_not_exist = AuthError::UserDoesNotExist {
+            name: "john".to_string(),
+        };
+        assert_eq!(
+            user_does_not_exist.to_string(),
+            "User `john` does not exist"
+        );
+        assert_eq!(
+            AuthError::InvalidCredentials.to_string(),
+            "Invalid credentials"
+        );
+        #[cfg(not(feature = "auto_display"))]
+        assert_eq!(AuthError::NoDoc.to_string(), "AuthError::NoDoc");
+        let io = AuthError::Io(std::io::Error::other("disk full"));
+        assert_eq!(io.to_string(), "Io failure: disk full");
+
+        assert_eq!(LoginError::TooManyAttempts.to_string(), "Too many attempts");
+        #[cfg(not(feature = "auto_display"))]
+        assert_eq!(LoginError::Locked.to_string(), "LoginError::Locked");
+        let login: LoginError = user_does_not_exist.into();
+        assert_eq!(login.to_string(), "User `john` does not exist");
+    }
+}
+
+#[cfg(test)]
+pub mod should_not_compile_tests {
+
+    #[test]
+    fn default_on_non_source_struct() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/default_on_non_source_struct.rs");
+    }
+
+    #[test]
+    fn depends_on_self() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/depends_on_itself.rs");
+    }
+
+    #[test]
+    fn display_unknown_field() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/display_unknown_field.rs");
+    }
+
+    #[test]
+    fn did_you_mean_disable_from() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/did_you_mean_disable_from.rs");
+    }
+
+    #[test]
+    fn did_you_mean_set() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/did_you_mean_set.rs");
+    }
+
+    #[test]
+    fn error_sources_of_diffrent_names() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/error_sources_of_diffrent_names.rs");
+    }
+
+    #[test]
+    fn floating_attributes() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/floating_attributes.rs");
+    }
+
+    #[test]
+    fn recursive_generic_dependency() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/recursive_generic_dependency.rs");
+    }
+
+    #[test]
+    fn set_assertions() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/set_assertions.rs");
+    }
+
+    #[test]
+    fn multiple_parse_errors() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/multiple_parse_errors.rs");
+    }
+
+    #[test]
+    fn multiple_resolve_errors() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/multiple_resolve_errors.rs");
+    }
+
+    #[test]
+    fn multiple_validation_errors() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/multiple_validation_errors.rs");
+    }
+
+    #[test]
+    fn multiple_from_sources() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/multiple_from_sources.rs");
+    }
+
+    #[test]
+    fn errors_attribute_without_placeholder() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/errors_attribute_without_placeholder.rs");
+    }
+
+    #[test]
+    fn errors_attribute_foreign_set() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/errors_attribute_foreign_set.rs");
+    }
+
+    #[test]
+    fn contains_other_variant() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/contains_other_variant.rs");
+    }
+
+    #[test]
+    fn generic_specification_needed() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/generic_specification_needed.rs");
+    }
+
+    #[test]
+    fn no_from_for_multiple_of_same_type() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/no_from_for_multiple_of_same_type.rs");
+    }
+
+    #[test]
+    fn maps_to_different_shape() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/maps_to_different_shape.rs");
+    }
+
+    #[test]
+    fn standalone_generic() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/standalone_generic.rs");
+    }
+
+    #[test]
+    fn debug_mode() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/debug_mode.rs");
+    }
+
+    #[test]
+    fn set_display_not_auto() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/set_display_not_auto.rs");
+    }
+
+    #[test]
+    fn display_template() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/display_template.rs");
+    }
+
+    #[test]
+    fn doc_display() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/doc_display.rs");
+    }
+
+    #[test]
+    fn doc_display_without_doc() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/trybuild/doc_display_without_doc.rs");
     }
 
     #[test]
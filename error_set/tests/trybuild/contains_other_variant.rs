@@ -0,0 +1,16 @@
+use error_set::{error_set, variant, Contains};
+
+error_set! {
+    DownloadError = {
+        TimedOut,
+        Timeout2,
+    };
+}
+
+fn has_timeout<E: Contains<variant!(Timeout)>>(_error: &E) -> bool {
+    true
+}
+
+pub fn main() {
+    has_timeout(&DownloadError::TimedOut);
+}
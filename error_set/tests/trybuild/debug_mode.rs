@@ -0,0 +1,15 @@
+use error_set::error_set;
+
+error_set! {
+    #[debug(compat)]
+    X = {
+        A,
+    };
+    #[debug(display)]
+    #[disable(Debug)]
+    Y = {
+        B,
+    };
+}
+
+pub fn main() {}
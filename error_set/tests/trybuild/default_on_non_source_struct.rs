@@ -0,0 +1,12 @@
+use error_set::error_set;
+
+error_set! {
+    ConfigError = {
+        InvalidKey {
+            #[default]
+            key: String,
+        },
+    };
+}
+
+pub fn main() {}
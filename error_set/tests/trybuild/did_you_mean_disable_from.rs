@@ -0,0 +1,14 @@
+use error_set::error_set;
+
+error_set! {
+    BookParsingError = {
+        MissingBookDescription,
+        IoError(std::io::Error),
+    };
+    #[disable(From(BookParsingEror, std::io::Eror))]
+    BookSectionParsingError = {
+        MissingNameArg,
+    } || BookParsingError;
+}
+
+pub fn main() {}
@@ -0,0 +1,13 @@
+use error_set::error_set;
+
+error_set! {
+    BookParsingError = {
+        MissingBookDescription,
+        IoError(std::io::Error),
+    };
+    BookSectionParsingError = {
+        MissingNameArg,
+    } || BookParsingEror;
+}
+
+pub fn main() {}
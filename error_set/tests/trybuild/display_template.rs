@@ -0,0 +1,14 @@
+use error_set::error_set;
+
+error_set! {
+    #[display_template("[{code}] {message}")]
+    X = {
+        A,
+    };
+    #[display_prefix("y: ", kept)]
+    Y = {
+        B,
+    };
+}
+
+pub fn main() {}
@@ -0,0 +1,21 @@
+use error_set::error_set;
+
+error_set! {
+    AuthError = {
+        #[display("User `{name}` with role `{rol}` does not exist")]
+        UserDoesNotExist {
+            name: String,
+            role: u32,
+        },
+        #[display("The provided credentials are invalid: {}")]
+        InvalidCredentials,
+        #[display("Io error {1}")]
+        IoError(std::io::Error),
+        #[display("Missing {name")]
+        Missing {
+            name: String,
+        },
+    };
+}
+
+pub fn main() {}
@@ -0,0 +1,13 @@
+use error_set::error_set;
+
+error_set! {
+    #[display(doc)]
+    X = {
+        /// User {nme} does not exist
+        A {
+            name: String,
+        },
+    };
+}
+
+pub fn main() {}
@@ -0,0 +1,10 @@
+use error_set::error_set;
+
+error_set! {
+    X = {
+        #[display(doc)]
+        A,
+    };
+}
+
+pub fn main() {}
@@ -0,0 +1,8 @@
+use error_set::errors;
+
+#[errors(std::fmt::Error || { Timeout })]
+fn download() -> Result<Vec<u8>, _> {
+    Ok(Vec::new())
+}
+
+pub fn main() {}
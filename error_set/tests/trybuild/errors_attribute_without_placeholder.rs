@@ -0,0 +1,8 @@
+use error_set::errors;
+
+#[errors({ Timeout })]
+fn download() -> Result<Vec<u8>, std::io::Error> {
+    Ok(Vec::new())
+}
+
+pub fn main() {}
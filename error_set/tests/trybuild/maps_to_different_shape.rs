@@ -0,0 +1,19 @@
+use error_set::error_set;
+
+error_set! {
+    NetworkError = {
+        TimedOut {
+            after_ms: u64,
+        },
+    };
+    DownloadError = {
+        #[maps_to(NetworkError::TimedOut)]
+        Timeout,
+    };
+    UploadError = {
+        #[maps_to(NetworkError::Closed)]
+        Timeout,
+    };
+}
+
+pub fn main() {}
@@ -0,0 +1,15 @@
+use error_set::error_set;
+
+error_set! {
+    X = {
+        #[from]
+        IoError1(std::io::Error),
+    };
+    Y = {
+        #[from]
+        IoError2(std::io::Error),
+    };
+    Z = X || Y;
+}
+
+pub fn main() {}
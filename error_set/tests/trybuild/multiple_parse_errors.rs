@@ -0,0 +1,16 @@
+use error_set::error_set;
+
+error_set! {
+    ParseError = {
+        MissingComma
+        Eof,
+    };
+    UploadError = {
+        Timeout,
+    };
+    DownloadError = {
+        Closed = 1,
+    };
+}
+
+pub fn main() {}
@@ -0,0 +1,15 @@
+use error_set::error_set;
+
+error_set! {
+    UploadError = {
+        Timeout,
+    } || UnknownError;
+    DownloadError = {
+        #[display("Closed by {nme}")]
+        Closed {
+            name: String,
+        },
+    } || MissingError;
+}
+
+pub fn main() {}
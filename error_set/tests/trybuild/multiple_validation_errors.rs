@@ -0,0 +1,17 @@
+use error_set::error_set;
+
+error_set! {
+    UploadError = {
+        Timeout,
+        Timeout,
+    };
+    DownloadError = {
+        Closed,
+        Closed,
+    };
+    DownloadError = {
+        Reset,
+    };
+}
+
+pub fn main() {}
@@ -0,0 +1,15 @@
+use error_set::error_set;
+
+error_set! {
+    X<T: std::error::Error> = {
+        A(T),
+    } || Y<T>;
+    Y<T: std::error::Error> = {
+        B(T),
+    } || Z<std::io::Error>;
+    Z<T: std::error::Error> = {
+        C(T),
+    } || X<std::fmt::Error>;
+}
+
+pub fn main() {}
@@ -0,0 +1,32 @@
+use error_set::error_set;
+
+error_set! {
+    #[assert_subset_of(MediaError)]
+    #[assert_disjoint_with(UploadError, UnknownEror)]
+    DownloadError = {
+        TimedOut,
+        InvalidUrl,
+        IoError(std::io::Error),
+    };
+    MediaError = {
+        TimedOut,
+        IoError(std::io::Error),
+    };
+    UploadError = {
+        TimedOut,
+        TooLarge,
+    };
+    UnknownError = {
+        Unknown,
+    };
+    #[assert_subset_of(ArchiveError)]
+    CacheError = {
+        Expired,
+    };
+    #[disable(From(CacheError))]
+    ArchiveError = CacheError || {
+        Corrupted,
+    };
+}
+
+pub fn main() {}
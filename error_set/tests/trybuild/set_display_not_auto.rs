@@ -0,0 +1,10 @@
+use error_set::error_set;
+
+error_set! {
+    #[display("X happened")]
+    X = {
+        A,
+    };
+}
+
+pub fn main() {}
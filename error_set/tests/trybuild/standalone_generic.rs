@@ -0,0 +1,22 @@
+use error_set::error_set;
+
+error_set! {
+    X<T: std::error::Error> = {
+        #[standalone]
+        A(T),
+    };
+    Y = {
+        #[standalone]
+        B {
+            value: u32,
+        },
+    };
+    Z = {
+        #[standalone]
+        B {
+            value: String,
+        },
+    };
+}
+
+pub fn main() {}
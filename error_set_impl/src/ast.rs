@@ -1,4 +1,5 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
 use syn::{
     braced, parenthesized,
     parse::{Parse, ParseBuffer, ParseStream},
@@ -8,49 +9,129 @@ use syn::{
     Attribute, Ident, Result, TypeParam,
 };
 
+use crate::validate::{format_placeholders, Placeholder};
+use crate::{combine_error, did_you_mean};
+
 const DISPLAY_ATTRIBUTE_NAME: &str = "display";
 const DISABLE_ATTRIBUTE_NAME: &str = "disable";
+const MAPS_TO_ATTRIBUTE_NAME: &str = "maps_to";
+const DEFAULT_ATTRIBUTE_NAME: &str = "default";
+const REDACT_ATTRIBUTE_NAME: &str = "redact";
+const FROM_ATTRIBUTE_NAME: &str = "from";
+const STANDALONE_ATTRIBUTE_NAME: &str = "standalone";
+const RETRYABLE_ATTRIBUTE_NAME: &str = "retryable";
+const SEVERITY_ATTRIBUTE_NAME: &str = "severity";
+const SEVERITY_LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+const USER_MESSAGE_ATTRIBUTE_NAME: &str = "user_message";
+const SET_DISPLAY_MODES: [&str; 2] = ["auto", "doc"];
+const DISPLAY_PREFIX_ATTRIBUTE_NAME: &str = "display_prefix";
+const DISPLAY_TEMPLATE_ATTRIBUTE_NAME: &str = "display_template";
+const DISPLAY_TEMPLATE_PLACEHOLDERS: [&str; 2] = ["message", "variant"];
+const DEBUG_ATTRIBUTE_NAME: &str = "debug";
+const DEBUG_MODES: [&str; 2] = ["display", "compact"];
+const ASSERT_SUBSET_OF_ATTRIBUTE_NAME: &str = "assert_subset_of";
+const ASSERT_DISJOINT_WITH_ATTRIBUTE_NAME: &str = "assert_disjoint_with";
 
 #[derive(Clone)]
 pub(crate) struct AstErrorSet {
+    /// The path the generated code refers to this crate by, declared with `crate = path;`
+    pub(crate) crate_path: syn::Path,
     pub(crate) set_items: Vec<AstErrorDeclaration>,
 }
 
 impl Parse for AstErrorSet {
     fn parse(input: ParseStream) -> Result<Self> {
+        let crate_path = match parse_crate_path(input)? {
+            Some(crate_path) => {
+                input.parse::<token::Semi>()?;
+                crate_path
+            }
+            None => default_crate_path(),
+        };
         let mut set_items = Vec::new();
+        let mut errors = None;
 
         while !input.is_empty() {
-            let fork = input.fork();
-            let set_item = match input.parse::<AstErrorDeclaration>() {
-                Ok(value) => value,
+            match parse_declaration(input) {
+                Ok(set_item) => set_items.push(set_item),
                 Err(error) => {
-                    if input.is_empty() {
-                        return Err(syn::Error::new(last_token_span(fork), error.to_string()));
-                    } else {
-                        return Err(error);
-                    }
+                    combine_error(&mut errors, error);
+                    // Continue with the next declaration, so independent errors are reported together.
+                    skip_past_semi(input)?;
                 }
-            };
-            set_items.push(set_item);
-            if input.peek(token::Semi) {
-                input.parse::<token::Semi>().unwrap();
+            }
+        }
+        if let Some(errors) = errors {
+            return Err(errors);
+        }
+        Ok(AstErrorSet {
+            crate_path,
+            set_items,
+        })
+    }
+}
+
+/// Parses `crate = path`, the path of this crate for the generated code to use when it is not a direct dependency
+/// named `error_set` - e.g. `crate = my_crate::error_set`, if next.
+fn parse_crate_path(input: ParseStream) -> Result<Option<syn::Path>> {
+    if !(input.peek(token::Crate) && input.peek2(token::Eq)) {
+        return Ok(None);
+    }
+    input.parse::<token::Crate>().unwrap();
+    input.parse::<token::Eq>().unwrap();
+    syn::Path::parse_mod_style(input).map(Some)
+}
+
+/// The path of this crate when not declared with `crate = path`.
+pub(crate) fn default_crate_path() -> syn::Path {
+    syn::parse_quote! { ::error_set }
+}
+
+/// Parses a declaration and the `;` that follows it.
+fn parse_declaration(input: ParseStream) -> Result<AstErrorDeclaration> {
+    let fork = input.fork();
+    let set_item = match input.parse::<AstErrorDeclaration>() {
+        Ok(value) => value,
+        Err(error) => {
+            if input.is_empty() {
+                return Err(syn::Error::new(last_token_span(fork), error.to_string()));
             } else {
-                if input.is_empty() {
-                    return Err(syn::Error::new(
-                        last_token_span(fork),
-                        "Expected a `;` after an error definition.",
-                    ));
-                } else {
-                    return Err(syn::Error::new(
-                        input.span(),
-                        "Expected a `;` after an error definition.",
-                    ));
-                }
+                return Err(error);
             }
         }
-        Ok(AstErrorSet { set_items })
+    };
+    if input.peek(token::Semi) {
+        input.parse::<token::Semi>().unwrap();
+    } else {
+        if input.is_empty() {
+            return Err(syn::Error::new(
+                last_token_span(fork),
+                "Expected a `;` after an error definition.",
+            ));
+        } else {
+            return Err(syn::Error::new(
+                input.span(),
+                "Expected a `;` after an error definition.",
+            ));
+        }
     }
+    Ok(set_item)
+}
+
+/// Skips the tokens up to and including the next top level `;`.
+fn skip_past_semi(input: ParseStream) -> Result<()> {
+    input.step(|cursor| {
+        let mut rest = *cursor;
+        while let Some((token_tree, next)) = rest.token_tree() {
+            rest = next;
+            if let TokenTree::Punct(punct) = token_tree {
+                if punct.as_char() == ';' {
+                    break;
+                }
+            }
+        }
+        Ok(((), rest))
+    })
 }
 
 #[derive(Clone)]
@@ -59,6 +140,15 @@ pub(crate) struct AstErrorDeclaration {
     pub(crate) error_name: Ident,
     pub(crate) generics: Vec<TypeParam>,
     pub(crate) disabled: Disabled,
+    pub(crate) assertions: SetAssertions,
+    /// The default `#[user_message("..")]` of variants that do not declare one
+    pub(crate) user_message: Option<syn::LitStr>,
+    /// Wraps the display of every variant, declared with `#[display_prefix(..)]` or `#[display_template(..)]`
+    pub(crate) display_template: Option<DisplayTemplate>,
+    /// How the generated `Debug` is written, declared with `#[debug(..)]`
+    pub(crate) debug: Option<DebugMode>,
+    /// If variants without `#[display(..)]` are displayed in words, declared with `#[display(auto)]`
+    pub(crate) auto_display: bool,
     pub(crate) parts: Vec<AstInlineOrRefError>,
 }
 
@@ -66,6 +156,31 @@ impl Parse for AstErrorDeclaration {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut attributes = input.call(Attribute::parse_outer)?;
         let disabled = extract_disabled(&mut attributes)?;
+        let assertions = SetAssertions {
+            subset_of: extract_set_list_attribute(
+                &mut attributes,
+                ASSERT_SUBSET_OF_ATTRIBUTE_NAME,
+            )?,
+            disjoint_with: extract_set_list_attribute(
+                &mut attributes,
+                ASSERT_DISJOINT_WITH_ATTRIBUTE_NAME,
+            )?,
+        };
+        let user_message = extract_str_attribute(&mut attributes, USER_MESSAGE_ATTRIBUTE_NAME)?;
+        let display_template = extract_display_template_attribute(&mut attributes)?;
+        let debug = extract_debug_attribute(&mut attributes)?;
+        let display_modes = extract_set_display_modes(&mut attributes)?;
+        let auto_display = display_modes.iter().any(|e| e == "auto");
+        let doc_display = display_modes.iter().any(|e| e == "doc");
+        if let (Some(debug), true) = (&debug, disabled.debug) {
+            return Err(syn::Error::new(
+                debug.span(),
+                format!(
+                    "`{}` cannot be used together with `{}(Debug)`.",
+                    DEBUG_ATTRIBUTE_NAME, DISABLE_ATTRIBUTE_NAME
+                ),
+            ));
+        }
         if input.is_empty() {
             return Err(syn::Error::new(
                 input.span(),
@@ -111,16 +226,76 @@ impl Parse for AstErrorDeclaration {
                 "Missing error definitions",
             ));
         }
+        if doc_display {
+            for part in parts.iter_mut() {
+                let AstInlineOrRefError::Inline(inline_part) = part else {
+                    continue;
+                };
+                for variant in inline_part.error_variants.iter_mut() {
+                    if variant.display.is_none() {
+                        variant.display = doc_display_attribute(&variant.attributes);
+                    }
+                }
+            }
+        }
         return Ok(AstErrorDeclaration {
             attributes,
             error_name,
             generics,
             disabled,
+            assertions,
+            user_message,
+            display_template,
+            debug,
+            auto_display,
             parts,
         });
     }
 }
 
+/// The arguments of `#[errors(..)]` on a function - the parts of an error set, e.g. `X || Y || { Z }`
+pub(crate) struct AstErrorsAttribute {
+    /// The path the generated code refers to this crate by, declared with `crate = path,`
+    pub(crate) crate_path: syn::Path,
+    pub(crate) parts: Vec<AstInlineOrRefError>,
+}
+
+impl Parse for AstErrorsAttribute {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let crate_path = match parse_crate_path(input)? {
+            Some(crate_path) => {
+                input.parse::<token::Comma>()?;
+                crate_path
+            }
+            None => default_crate_path(),
+        };
+        let mut parts = Vec::new();
+        while !input.is_empty() {
+            // The declarations of a referenced set are found through a crate local macro, see `errors_attribute::missing_ref`
+            if input.peek(token::PathSep) || (input.peek(Ident) && input.peek2(token::PathSep)) {
+                let path = input.parse::<syn::Path>()?;
+                return Err(syn::Error::new_spanned(
+                    path,
+                    "`#[errors(..)]` can only reference error sets declared with `error_set!` in the same crate, by \
+                    name. e.g. `DownloadError` after `use crate::errors::DownloadError;`.",
+                ));
+            }
+            parts.push(input.parse::<AstInlineOrRefError>()?);
+            if input.is_empty() {
+                break;
+            }
+            if !input.peek(token::OrOr) {
+                return Err(syn::Error::new(input.span(), "Expected `||` to be next."));
+            }
+            input.parse::<token::OrOr>().unwrap();
+        }
+        if parts.is_empty() {
+            return Err(syn::Error::new(input.span(), "Missing error definitions"));
+        }
+        Ok(AstErrorsAttribute { crate_path, parts })
+    }
+}
+
 #[derive(Clone)]
 pub(crate) enum AstInlineOrRefError {
     Inline(AstInlineError),
@@ -175,7 +350,8 @@ impl Parse for AstInlineError {
 #[derive(Clone)]
 pub(crate) struct RefError {
     pub(crate) name: Ident,
-    pub(crate) generic_refs: Vec<Ident>,
+    /// The generics declared for the reference - `T` and `String` in `X<T> || Y<String>`
+    pub(crate) generic_refs: Vec<syn::Type>,
 }
 
 impl Parse for RefError {
@@ -197,6 +373,23 @@ pub(crate) struct AstErrorVariant {
     pub(crate) attributes: Vec<Attribute>,
     pub(crate) cfg_attributes: Vec<Attribute>,
     pub(crate) display: Option<DisplayAttribute>,
+    /// Explicit `#[maps_to(Set::Variant)]` conversion targets
+    pub(crate) maps_to: Vec<MapsTo>,
+    /// If `#[from]` was declared - the variant `From<source>` converts into when multiple variants have the same source
+    pub(crate) from_source: bool,
+    /// If `#[standalone]` was declared - a struct of the same name and shape is generated, that converts into every
+    /// error enum with this variant
+    pub(crate) standalone: bool,
+    /// If `#[retryable]` was declared, with the optional delay before retrying
+    pub(crate) retryable: Option<Retryable>,
+    /// The level declared with `#[severity(..)]` - e.g. `warn`
+    pub(crate) severity: Option<Ident>,
+    /// The message declared with `#[user_message("..")]`, safe to show to end users
+    pub(crate) user_message: Option<syn::LitStr>,
+    /// The `#[display_prefix(..)]` or `#[display_template(..)]` of the set that declared this variant
+    pub(crate) display_template: Option<DisplayTemplate>,
+    /// The `#[user_message("..")]` of the set that declared this variant, used if the variant does not declare one
+    pub(crate) default_user_message: Option<syn::LitStr>,
     pub(crate) name: Ident,
     // Dev Note: `Some(Vec::new())` == `{}`, `Some(Vec::new(..))` == `{..}`, `None` == ``. `{}` means inline struct if has source as well.
     pub(crate) fields: Option<Vec<AstInlineErrorVariantField>>,
@@ -209,7 +402,28 @@ impl Parse for AstErrorVariant {
     fn parse(input: ParseStream) -> Result<Self> {
         let attributes = input.call(Attribute::parse_outer)?;
         let (mut attributes, cfg_attributes) = extract_cfg(attributes);
-        let display = extract_display_attribute(&mut attributes)?;
+        let mut display = extract_display_attribute(&mut attributes)?;
+        if let Some(doc) = display.as_ref().filter(|e| is_doc(&e.tokens)) {
+            let Some(doc_display) = doc_display_attribute(&attributes) else {
+                return Err(syn::parse::Error::new(
+                    doc.tokens.span(),
+                    format!(
+                        "`{}(doc)` requires a doc comment on the variant.",
+                        DISPLAY_ATTRIBUTE_NAME
+                    ),
+                ));
+            };
+            display = Some(doc_display);
+        }
+        let maps_to = extract_maps_to_attribute(&mut attributes)?;
+        let from_source = extract_marker_attribute(&mut attributes, FROM_ATTRIBUTE_NAME);
+        let standalone = extract_marker_attribute(&mut attributes, STANDALONE_ATTRIBUTE_NAME);
+        let retryable = extract_retryable_attribute(&mut attributes)?;
+        let severity = extract_severity_attribute(&mut attributes)?;
+        let user_message = extract_str_attribute(&mut attributes, USER_MESSAGE_ATTRIBUTE_NAME)?;
+        // Set by the declaring error set when resolved
+        let display_template = None;
+        let default_user_message = None;
         let name = input.parse::<Ident>()?;
         let content: syn::Result<_> = (|| {
             let content;
@@ -243,10 +457,21 @@ impl Parse for AstErrorVariant {
         })();
         let content = match content {
             Err(_) => {
+                if from_source && source_type.is_none() {
+                    return Err(from_source_error(&name));
+                }
                 return Ok(AstErrorVariant {
                     attributes,
                     cfg_attributes,
                     display,
+                    maps_to,
+                    from_source,
+                    standalone,
+                    retryable,
+                    severity,
+                    user_message,
+                    display_template,
+                    default_user_message,
                     name,
                     fields: None,
                     source_type,
@@ -259,11 +484,33 @@ impl Parse for AstErrorVariant {
             .parse_terminated(AstInlineErrorVariantField::parse, syn::Token![,])?
             .into_iter()
             .collect::<Vec<_>>();
+        if source_type.is_none() {
+            if let Some(field) = fields.iter().find(|e| e.default) {
+                return Err(syn::parse::Error::new(
+                    field.name.span(),
+                    format!(
+                        "`#[{}]` is only supported on the fields of source struct variants - e.g. `{}(std::io::Error) {{..}}`.",
+                        DEFAULT_ATTRIBUTE_NAME, name
+                    ),
+                ));
+            }
+        }
+        if from_source && (source_type.is_none() || fields.iter().any(|e| !e.default)) {
+            return Err(from_source_error(&name));
+        }
         let fields = Some(fields);
         Ok(AstErrorVariant {
             attributes,
             cfg_attributes,
             display,
+            maps_to,
+            from_source,
+            standalone,
+            retryable,
+            severity,
+            user_message,
+            display_template,
+            default_user_message,
             name,
             fields,
             source_type,
@@ -272,6 +519,16 @@ impl Parse for AstErrorVariant {
     }
 }
 
+fn from_source_error(name: &Ident) -> syn::Error {
+    syn::parse::Error::new(
+        name.span(),
+        format!(
+            "`#[{}]` is only supported on variants that can be created from the source alone - e.g. `{}(std::io::Error)` or `{}(std::io::Error) {{}}`.",
+            FROM_ATTRIBUTE_NAME, name, name
+        ),
+    )
+}
+
 //************************************************************************//
 
 fn generics<T: Parse>(input: &ParseStream) -> Result<Vec<T>> {
@@ -346,12 +603,7 @@ fn extract_disabled(attributes: &mut Vec<Attribute>) -> syn::Result<Disabled> {
     if to_remove.is_empty() {
         return Ok(disabled);
     }
-    let mut index = 0;
-    attributes.retain(|_| {
-        let retain = !&to_remove.contains(&index);
-        index += 1;
-        return retain;
-    });
+    remove_attributes(attributes, &to_remove);
 
     Ok(disabled)
 }
@@ -432,9 +684,10 @@ fn extract_disabled_helper(attribute: &Attribute) -> syn::Result<Option<Disabled
                     }
                     _ => {
                         return Err(syn::parse::Error::new(
-                            ident.span(),
+                            name.span(),
                             format!(
-                                "`{ident}` is not a valid option for `{DISABLE_ATTRIBUTE_NAME}`"
+                                "`{ident}` is not a valid option for `{DISABLE_ATTRIBUTE_NAME}`.{}",
+                                did_you_mean(&ident, ["From", "Display", "Debug", "Error"])
                             ),
                         ))
                     }
@@ -512,12 +765,7 @@ fn extract_display_attribute(
     if to_remove.is_empty() {
         return Ok(Some(display));
     }
-    let mut index = 0;
-    attributes.retain(|_| {
-        let retain = !&to_remove.contains(&index);
-        index += 1;
-        return retain;
-    });
+    remove_attributes(attributes, &to_remove);
     Ok(Some(display))
 }
 
@@ -541,6 +789,429 @@ fn display_tokens(attribute: &Attribute) -> Option<DisplayAttribute> {
     };
 }
 
+/// An explicit variant to variant mapping - `#[maps_to(Set::Variant)]`
+#[derive(Clone)]
+pub(crate) struct MapsTo {
+    pub(crate) error_name: Ident,
+    pub(crate) variant_name: Ident,
+}
+
+impl Parse for MapsTo {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let path = input.parse::<syn::Path>()?;
+        let mut segments = path.segments.iter();
+        match (segments.next(), segments.next(), segments.next()) {
+            (Some(error_name), Some(variant_name), None)
+                if error_name.arguments.is_none() && variant_name.arguments.is_none() =>
+            {
+                Ok(MapsTo {
+                    error_name: error_name.ident.clone(),
+                    variant_name: variant_name.ident.clone(),
+                })
+            }
+            _ => Err(syn::parse::Error::new(
+                path.span(),
+                format!(
+                    "Expected `{}` arguments of the form `ErrorSet::Variant`.",
+                    MAPS_TO_ATTRIBUTE_NAME
+                ),
+            )),
+        }
+    }
+}
+
+fn extract_maps_to_attribute(attributes: &mut Vec<Attribute>) -> syn::Result<Vec<MapsTo>> {
+    let mut maps_to = Vec::new();
+    let mut to_remove = Vec::new();
+    for (i, e) in attributes.iter().enumerate() {
+        let syn::Meta::List(list) = &e.meta else {
+            continue;
+        };
+        if !list.path.is_ident(MAPS_TO_ATTRIBUTE_NAME) {
+            continue;
+        }
+        let punc = syn::parse::Parser::parse2(
+            &|input: ParseStream| Punctuated::<MapsTo, token::Comma>::parse_terminated(input),
+            list.tokens.clone(),
+        )?;
+        maps_to.extend(punc);
+        to_remove.push(i);
+    }
+    remove_attributes(attributes, &to_remove);
+    Ok(maps_to)
+}
+
+/// `#[retryable]` or `#[retryable(after_ms = 100)]` on a variant
+#[derive(Clone)]
+pub(crate) struct Retryable {
+    /// The delay before retrying, in milliseconds
+    pub(crate) after_ms: Option<syn::LitInt>,
+}
+
+fn extract_retryable_attribute(attributes: &mut Vec<Attribute>) -> syn::Result<Option<Retryable>> {
+    let mut retryable = None;
+    let mut to_remove = Vec::new();
+    for (i, e) in attributes.iter().enumerate() {
+        if !e.path().is_ident(RETRYABLE_ATTRIBUTE_NAME) {
+            continue;
+        }
+        if retryable.is_some() {
+            return Err(syn::parse::Error::new_spanned(
+                e,
+                format!(
+                    "More than one `{}` attribute found",
+                    RETRYABLE_ATTRIBUTE_NAME
+                ),
+            ));
+        }
+        let after_ms = match &e.meta {
+            syn::Meta::Path(_) => None,
+            syn::Meta::List(list) => {
+                let name_value = syn::parse2::<syn::MetaNameValue>(list.tokens.clone())?;
+                if !name_value.path.is_ident("after_ms") {
+                    let name = name_value.path.to_token_stream().to_string();
+                    return Err(syn::parse::Error::new_spanned(
+                        &name_value.path,
+                        format!(
+                            "Unknown `{}` option `{}`. Expected `after_ms = ..`.{}",
+                            RETRYABLE_ATTRIBUTE_NAME,
+                            name,
+                            did_you_mean(&name, ["after_ms"])
+                        ),
+                    ));
+                }
+                match name_value.value {
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Int(after_ms),
+                        ..
+                    }) => Some(after_ms),
+                    value => {
+                        return Err(syn::parse::Error::new_spanned(
+                            value,
+                            "Expected `after_ms` to be an integer number of milliseconds.",
+                        ))
+                    }
+                }
+            }
+            syn::Meta::NameValue(_) => {
+                return Err(syn::parse::Error::new_spanned(
+                    e,
+                    "Expected `#[retryable]` or `#[retryable(after_ms = ..)]`.",
+                ))
+            }
+        };
+        retryable = Some(Retryable { after_ms });
+        to_remove.push(i);
+    }
+    remove_attributes(attributes, &to_remove);
+    Ok(retryable)
+}
+
+/// Extracts the level of `#[severity(..)]`, one of [SEVERITY_LEVELS]
+fn extract_severity_attribute(attributes: &mut Vec<Attribute>) -> syn::Result<Option<Ident>> {
+    let mut severity = None;
+    let mut to_remove = Vec::new();
+    for (i, e) in attributes.iter().enumerate() {
+        if !e.path().is_ident(SEVERITY_ATTRIBUTE_NAME) {
+            continue;
+        }
+        if severity.is_some() {
+            return Err(syn::parse::Error::new_spanned(
+                e,
+                format!(
+                    "More than one `{}` attribute found",
+                    SEVERITY_ATTRIBUTE_NAME
+                ),
+            ));
+        }
+        let level = e.parse_args::<Ident>()?;
+        let level_string = level.to_string();
+        if !SEVERITY_LEVELS.contains(&level_string.as_str()) {
+            return Err(syn::parse::Error::new(
+                level.span(),
+                format!(
+                    "Unknown severity `{}`. Expected one of `{}`.{}",
+                    level_string,
+                    SEVERITY_LEVELS.join("`, `"),
+                    did_you_mean(&level_string, SEVERITY_LEVELS)
+                ),
+            ));
+        }
+        severity = Some(level);
+        to_remove.push(i);
+    }
+    remove_attributes(attributes, &to_remove);
+    Ok(severity)
+}
+
+/// The template of `#[display_prefix(..)]` or `#[display_template(..)]` on an error set, which wraps the display of
+/// each variant
+#[derive(Clone)]
+pub(crate) struct DisplayTemplate {
+    /// A format string with the [DISPLAY_TEMPLATE_PLACEHOLDERS]. A prefix is turned into `"prefix{message}"`.
+    pub(crate) template: syn::LitStr,
+    /// If variants aggregated with `||` keep the template of their own set, instead of having it replaced
+    pub(crate) keep: bool,
+}
+
+/// Extracts `#[display_prefix("..")]` or `#[display_template("..")]`, each optionally followed by `, keep`
+fn extract_display_template_attribute(
+    attributes: &mut Vec<Attribute>,
+) -> syn::Result<Option<DisplayTemplate>> {
+    let mut display_template = None;
+    let mut to_remove = Vec::new();
+    for (i, e) in attributes.iter().enumerate() {
+        let is_prefix = e.path().is_ident(DISPLAY_PREFIX_ATTRIBUTE_NAME);
+        if !is_prefix && !e.path().is_ident(DISPLAY_TEMPLATE_ATTRIBUTE_NAME) {
+            continue;
+        }
+        if display_template.is_some() {
+            return Err(syn::parse::Error::new_spanned(
+                e,
+                format!(
+                    "Only one `{}` or `{}` attribute is allowed",
+                    DISPLAY_PREFIX_ATTRIBUTE_NAME, DISPLAY_TEMPLATE_ATTRIBUTE_NAME
+                ),
+            ));
+        }
+        let (value, keep) = e.parse_args_with(|input: ParseStream| {
+            let value = input.parse::<syn::LitStr>()?;
+            if input.is_empty() {
+                return Ok((value, false));
+            }
+            input.parse::<token::Comma>()?;
+            let keep = input.parse::<Ident>()?;
+            if keep != "keep" {
+                return Err(syn::parse::Error::new(
+                    keep.span(),
+                    "Expected `keep` to be next.",
+                ));
+            }
+            Ok((value, true))
+        })?;
+        let template = if is_prefix {
+            let prefix = value.value().replace('{', "{{").replace('}', "}}");
+            syn::LitStr::new(&format!("{}{{message}}", prefix), value.span())
+        } else {
+            validate_display_template(&value)?;
+            value
+        };
+        display_template = Some(DisplayTemplate { template, keep });
+        to_remove.push(i);
+    }
+    remove_attributes(attributes, &to_remove);
+    Ok(display_template)
+}
+
+/// Checks [template] only uses the [DISPLAY_TEMPLATE_PLACEHOLDERS], since it is not given any other arguments
+fn validate_display_template(template: &syn::LitStr) -> syn::Result<()> {
+    let placeholders = format_placeholders(&template.value())
+        .map_err(|message| syn::parse::Error::new(template.span(), message))?;
+    for placeholder in placeholders {
+        let name = match placeholder {
+            Placeholder::Named(name) if DISPLAY_TEMPLATE_PLACEHOLDERS.contains(&name.as_str()) => {
+                continue
+            }
+            Placeholder::Named(name) => name,
+            Placeholder::Positional(_) => String::new(),
+        };
+        return Err(syn::parse::Error::new(
+            template.span(),
+            format!(
+                "Unknown placeholder `{{{}}}` in `{}`. Expected one of `{{{}}}`.{}",
+                name,
+                DISPLAY_TEMPLATE_ATTRIBUTE_NAME,
+                DISPLAY_TEMPLATE_PLACEHOLDERS.join("}`, `{"),
+                did_you_mean(&name, DISPLAY_TEMPLATE_PLACEHOLDERS)
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// The mode of `#[debug(..)]` on an error set
+#[derive(Clone)]
+pub(crate) enum DebugMode {
+    /// The variant name followed by the `Display` message
+    Display(Ident),
+    /// Like the derived `Debug`, but sources are left out
+    Compact(Ident),
+}
+
+impl DebugMode {
+    fn span(&self) -> proc_macro2::Span {
+        match self {
+            DebugMode::Display(ident) | DebugMode::Compact(ident) => ident.span(),
+        }
+    }
+}
+
+/// Extracts the mode of `#[debug(..)]`, one of [DEBUG_MODES]
+fn extract_debug_attribute(attributes: &mut Vec<Attribute>) -> syn::Result<Option<DebugMode>> {
+    let mut debug = None;
+    let mut to_remove = Vec::new();
+    for (i, e) in attributes.iter().enumerate() {
+        if !e.path().is_ident(DEBUG_ATTRIBUTE_NAME) {
+            continue;
+        }
+        if debug.is_some() {
+            return Err(syn::parse::Error::new_spanned(
+                e,
+                format!("More than one `{}` attribute found", DEBUG_ATTRIBUTE_NAME),
+            ));
+        }
+        let mode = e.parse_args::<Ident>()?;
+        let mode_string = mode.to_string();
+        debug = Some(match &*mode_string {
+            "display" => DebugMode::Display(mode),
+            "compact" => DebugMode::Compact(mode),
+            _ => {
+                return Err(syn::parse::Error::new(
+                    mode.span(),
+                    format!(
+                        "Unknown debug mode `{}`. Expected one of `{}`.{}",
+                        mode_string,
+                        DEBUG_MODES.join("`, `"),
+                        did_you_mean(&mode_string, DEBUG_MODES)
+                    ),
+                ));
+            }
+        });
+        to_remove.push(i);
+    }
+    remove_attributes(attributes, &to_remove);
+    Ok(debug)
+}
+
+/// Extracts the modes of `#[display(..)]` on an error set, each one of [SET_DISPLAY_MODES]
+fn extract_set_display_modes(attributes: &mut Vec<Attribute>) -> syn::Result<Vec<Ident>> {
+    let mut display_modes = Vec::new();
+    let mut to_remove = Vec::new();
+    for (i, e) in attributes.iter().enumerate() {
+        if !e.path().is_ident(DISPLAY_ATTRIBUTE_NAME) {
+            continue;
+        }
+        match e.parse_args::<Ident>() {
+            Ok(ident) if SET_DISPLAY_MODES.contains(&ident.to_string().as_str()) => {
+                display_modes.push(ident)
+            }
+            _ => {
+                return Err(syn::parse::Error::new_spanned(
+                    e,
+                    format!(
+                        "Only `{0}(auto)` or `{0}(doc)` are allowed on an error set.",
+                        DISPLAY_ATTRIBUTE_NAME
+                    ),
+                ))
+            }
+        }
+        to_remove.push(i);
+    }
+    remove_attributes(attributes, &to_remove);
+    Ok(display_modes)
+}
+
+fn is_doc(tokens: &TokenStream) -> bool {
+    syn::parse2::<Ident>(tokens.clone()).is_ok_and(|e| e == "doc")
+}
+
+/// The first line of the doc comment in [attributes] as a display, for `#[display(doc)]`
+fn doc_display_attribute(attributes: &[Attribute]) -> Option<DisplayAttribute> {
+    for attribute in attributes {
+        if !attribute.path().is_ident("doc") {
+            continue;
+        }
+        let syn::Meta::NameValue(name_value) = &attribute.meta else {
+            continue;
+        };
+        let syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(doc),
+            ..
+        }) = &name_value.value
+        else {
+            continue;
+        };
+        let doc_value = doc.value();
+        let Some(line) = doc_value.lines().map(str::trim).find(|e| !e.is_empty()) else {
+            continue;
+        };
+        let line = syn::LitStr::new(line, doc.span());
+        return Some(DisplayAttribute {
+            tokens: line.into_token_stream(),
+        });
+    }
+    None
+}
+
+/// Extracts the string of a `#[name("..")]` attribute
+fn extract_str_attribute(
+    attributes: &mut Vec<Attribute>,
+    name: &str,
+) -> syn::Result<Option<syn::LitStr>> {
+    let mut value = None;
+    let mut to_remove = Vec::new();
+    for (i, e) in attributes.iter().enumerate() {
+        if !e.path().is_ident(name) {
+            continue;
+        }
+        if value.is_some() {
+            return Err(syn::parse::Error::new_spanned(
+                e,
+                format!("More than one `{}` attribute found", name),
+            ));
+        }
+        value = Some(e.parse_args::<syn::LitStr>()?);
+        to_remove.push(i);
+    }
+    remove_attributes(attributes, &to_remove);
+    Ok(value)
+}
+
+/// Compile time checks of how an error set relates to other error sets
+#[derive(Clone, Default)]
+pub(crate) struct SetAssertions {
+    /// `#[assert_subset_of(..)]` - every variant converts into these sets
+    pub(crate) subset_of: Vec<Ident>,
+    /// `#[assert_disjoint_with(..)]` - no variant is shared with these sets
+    pub(crate) disjoint_with: Vec<Ident>,
+}
+
+/// Extracts the error set names from all `#[name(..)]` attributes
+fn extract_set_list_attribute(
+    attributes: &mut Vec<Attribute>,
+    name: &str,
+) -> syn::Result<Vec<Ident>> {
+    let mut error_names = Vec::new();
+    let mut to_remove = Vec::new();
+    for (i, e) in attributes.iter().enumerate() {
+        let syn::Meta::List(list) = &e.meta else {
+            continue;
+        };
+        if !list.path.is_ident(name) {
+            continue;
+        }
+        let punc = match syn::parse::Parser::parse2(
+            &|input: ParseStream| Punctuated::<Ident, token::Comma>::parse_terminated(input),
+            list.tokens.clone(),
+        ) {
+            Ok(okay) => okay,
+            Err(_) => {
+                return Err(syn::parse::Error::new(
+                    list.tokens.span(),
+                    format!(
+                        "Expected `{}` arguments of the form `ErrorSet1, ErrorSet2`.",
+                        name
+                    ),
+                ))
+            }
+        };
+        error_names.extend(punc);
+        to_remove.push(i);
+    }
+    remove_attributes(attributes, &to_remove);
+    Ok(error_names)
+}
+
 /// old and new
 fn extract_cfg(attributes: Vec<Attribute>) -> (Vec<Attribute>, Vec<Attribute>) {
     let mut to_remove = Vec::new();
@@ -576,23 +1247,61 @@ fn extract_cfg(attributes: Vec<Attribute>) -> (Vec<Attribute>, Vec<Attribute>) {
     (attributes, cfgs)
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone)]
 pub(crate) struct AstInlineErrorVariantField {
+    pub(crate) attributes: Vec<Attribute>,
+    /// If `#[default]` was declared on the field
+    pub(crate) default: bool,
+    /// If `#[redact]` was declared on the field - it is displayed as `<redacted>`
+    pub(crate) redact: bool,
     pub(crate) name: Ident,
     pub(crate) r#type: syn::Type,
 }
 
 impl Parse for AstInlineErrorVariantField {
     fn parse(input: ParseStream) -> Result<Self> {
+        let mut attributes = input.call(Attribute::parse_outer)?;
+        let default = extract_marker_attribute(&mut attributes, DEFAULT_ATTRIBUTE_NAME);
+        let redact = extract_marker_attribute(&mut attributes, REDACT_ATTRIBUTE_NAME);
         let name: Ident = input.parse()?;
         let _: syn::Token![:] = input.parse()?;
         let r#type: syn::Type = input.parse()?;
-        Ok(AstInlineErrorVariantField { name, r#type })
+        Ok(AstInlineErrorVariantField {
+            attributes,
+            default,
+            redact,
+            name,
+            r#type,
+        })
+    }
+}
+
+/// Fields are the same if the name and type are the same, attributes do not change the shape of a variant.
+impl PartialEq for AstInlineErrorVariantField {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.r#type == other.r#type
     }
 }
 
 impl Eq for AstInlineErrorVariantField {}
 
+/// Removes the attributes at the indices [to_remove]
+fn remove_attributes(attributes: &mut Vec<Attribute>, to_remove: &[usize]) {
+    let mut index = 0;
+    attributes.retain(|_| {
+        let retain = !to_remove.contains(&index);
+        index += 1;
+        retain
+    });
+}
+
+/// Removes any attributes like `#[name]`, returning if there were any
+fn extract_marker_attribute(attributes: &mut Vec<Attribute>, name: &str) -> bool {
+    let len = attributes.len();
+    attributes.retain(|e| !matches!(&e.meta, syn::Meta::Path(path) if path.is_ident(name)));
+    return attributes.len() != len;
+}
+
 //************************************************************************//
 
 fn last_token_span(input: ParseBuffer) -> proc_macro2::Span {
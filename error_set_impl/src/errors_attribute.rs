@@ -0,0 +1,99 @@
+use quote::{format_ident, quote};
+use syn::{GenericArgument, Ident, PathArguments, ReturnType, Type};
+
+use crate::ast::{
+    AstErrorDeclaration, AstErrorSet, AstErrorsAttribute, AstInlineOrRefError, Disabled,
+    SetAssertions,
+};
+
+/// Turns `#[errors(..)]` on [item_fn] into an error set named after the function, and replaces the `_` error type of
+/// the function's return type with it. The sets it references are resolved with the declarations fetched by
+/// [missing_ref].
+pub(crate) fn errors(
+    errors_attribute: AstErrorsAttribute,
+    mut item_fn: syn::ItemFn,
+) -> syn::Result<(AstErrorSet, syn::ItemFn)> {
+    let error_name = error_name(&item_fn.sig.ident);
+    let error_type = placeholder_error_type(&mut item_fn.sig.output)?;
+    *error_type = Type::Verbatim(quote! { #error_name });
+
+    let fn_name = item_fn.sig.ident.to_string();
+    let doc = format!(" The errors of [`{fn_name}`].");
+    let declaration = AstErrorDeclaration {
+        attributes: vec![syn::parse_quote! { #[doc = #doc] }],
+        error_name,
+        generics: Vec::new(),
+        disabled: Disabled {
+            from: None,
+            display: false,
+            debug: false,
+            error: false,
+        },
+        assertions: SetAssertions::default(),
+        user_message: None,
+        display_template: None,
+        debug: None,
+        auto_display: false,
+        parts: errors_attribute.parts,
+    };
+    Ok((
+        AstErrorSet {
+            crate_path: errors_attribute.crate_path,
+            set_items: vec![declaration],
+        },
+        item_fn,
+    ))
+}
+
+/// The first set referenced by [errors_attribute] that is not declared in [declarations]. Each `error_set!` also
+/// declares a hidden macro, imported under the name of each of its sets, which is called with the name of the set to
+/// prepend the declarations of the invocation, since a proc macro cannot otherwise see the declarations of another
+/// invocation.
+pub(crate) fn missing_ref<'a>(
+    errors_attribute: &'a AstErrorsAttribute,
+    declarations: &[AstErrorDeclaration],
+) -> Option<&'a Ident> {
+    errors_attribute.parts.iter().find_map(|part| match part {
+        AstInlineOrRefError::Ref(ref_error)
+            if !declarations.iter().any(|e| e.error_name == ref_error.name) =>
+        {
+            Some(&ref_error.name)
+        }
+        _ => None,
+    })
+}
+
+/// e.g. `download_file` -> `DownloadFileError`
+fn error_name(fn_name: &Ident) -> Ident {
+    let mut name = String::new();
+    for word in fn_name.to_string().trim_start_matches("r#").split('_') {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.push_str(chars.as_str());
+        }
+    }
+    format_ident!("{}Error", name, span = fn_name.span())
+}
+
+/// The `_` in e.g. `-> Result<T, _>`, which the error set replaces.
+fn placeholder_error_type(output: &mut ReturnType) -> syn::Result<&mut Type> {
+    const MESSAGE: &str =
+        "Expected the return type to have `_` as its error type, e.g. `Result<T, _>`.";
+    let ReturnType::Type(_, r#type) = output else {
+        return Err(syn::Error::new(proc_macro2::Span::call_site(), MESSAGE));
+    };
+    let error = syn::Error::new_spanned(&*r#type, MESSAGE);
+    let Type::Path(type_path) = &mut **r#type else {
+        return Err(error);
+    };
+    let Some(PathArguments::AngleBracketed(arguments)) =
+        type_path.path.segments.last_mut().map(|e| &mut e.arguments)
+    else {
+        return Err(error);
+    };
+    match arguments.args.iter_mut().last() {
+        Some(GenericArgument::Type(error_type @ Type::Infer(_))) => Ok(error_type),
+        _ => Err(error),
+    }
+}
@@ -4,13 +4,18 @@
 use std::collections::{HashMap, HashSet};
 
 use proc_macro2::TokenStream;
-use quote::{quote, TokenStreamExt};
-use syn::{Attribute, Ident, Lit, TypeParam};
+use quote::{quote, ToTokens, TokenStreamExt};
+use syn::{ext::IdentExt, Attribute, Ident, Lit, TypeParam};
 
-use crate::ast::{AstInlineErrorVariantField, Disabled, DisplayAttribute};
+use crate::ast::{
+    AstInlineErrorVariantField, DebugMode, Disabled, DisplayAttribute, DisplayTemplate, MapsTo,
+    Retryable,
+};
+use crate::validate::{format_placeholders, Placeholder};
 
-/// Expand the [ErrorEnum]s into code.
-pub(crate) fn expand(error_enums: Vec<ErrorEnum>) -> TokenStream {
+/// Expand the [ErrorEnum]s into code. The code of the sets in [declared_elsewhere] is generated by the macro
+/// invocation that declared them, so only the `From`s to them from the other sets are generated here.
+pub(crate) fn expand(error_enums: Vec<ErrorEnum>, declared_elsewhere: &[Ident]) -> TokenStream {
     let mut token_stream = TokenStream::new();
     let mut graph: Vec<ErrorEnumGraphNode> = error_enums
         .into_iter()
@@ -26,19 +31,22 @@ pub(crate) fn expand(error_enums: Vec<ErrorEnum>) -> TokenStream {
 
             let mut variant_mappings = Vec::new();
             'look_for_next_variant_match: for (checking_variant_index, checking_variant) in graph
-                [checking_index]
+                [building_index]
                 .error_enum
-                .error_variants
+                .error_variants_of(&graph[checking_index].error_enum)
                 .iter()
                 .enumerate()
             {
-                for (building_variant_index, building_variant) in graph[building_index]
-                    .error_enum
-                    .error_variants
-                    .iter()
-                    .enumerate()
+                let building_enum = &graph[building_index].error_enum;
+                for (building_variant_index, building_variant) in
+                    building_enum.error_variants.iter().enumerate()
                 {
-                    if is_conversion_target(checking_variant, building_variant) {
+                    if is_mapped_conversion_target(
+                        checking_variant,
+                        building_enum,
+                        building_variant,
+                        graph.iter().map(|e| &e.error_enum),
+                    ) {
                         variant_mappings.push((checking_variant_index, building_variant_index));
                         continue 'look_for_next_variant_match;
                     }
@@ -52,20 +60,215 @@ pub(crate) fn expand(error_enums: Vec<ErrorEnum>) -> TokenStream {
     }
 
     for error_enum_node in graph.iter() {
-        add_code_for_node(error_enum_node, &*graph, &mut token_stream);
+        if declared_elsewhere.contains(&error_enum_node.error_enum.error_name) {
+            impl_froms(
+                error_enum_node,
+                &graph,
+                declared_elsewhere,
+                &mut token_stream,
+            );
+        } else {
+            add_code_for_node(
+                error_enum_node,
+                &graph,
+                declared_elsewhere,
+                &mut token_stream,
+            );
+        }
     }
+    impl_standalone_variants(&graph, declared_elsewhere, &mut token_stream);
     token_stream
 }
 
+/// Generates a struct for each `#[standalone]` variant, with the same name and shape, and a `From` for every error
+/// enum that contains the variant.
+fn impl_standalone_variants(
+    graph: &[ErrorEnumGraphNode],
+    declared_elsewhere: &[Ident],
+    token_stream: &mut TokenStream,
+) {
+    let mut generated: HashSet<&Ident> = HashSet::new();
+    for error_enum_node in graph {
+        let error_enum = &error_enum_node.error_enum;
+        let crate_path = &error_enum.crate_path;
+        for variant in error_enum.error_variants.iter() {
+            if !variant.is_standalone() || generated.contains(variant.name()) {
+                continue;
+            }
+            generated.insert(variant.name());
+            let is_declared_elsewhere = graph.iter().any(|e| {
+                declared_elsewhere.contains(&e.error_enum.error_name)
+                    && e.error_enum
+                        .error_variants
+                        .iter()
+                        .any(|e| is_conversion_target(variant, e))
+            });
+            let mut struct_token_stream = TokenStream::new();
+            let name = variant.name();
+            let attributes = variant.attributes();
+            let cfg_attributes = variant.cfg_attributes();
+            let (definition, pattern, construction) = match variant {
+                ErrorVariant::Named(_) => (quote! { ; }, quote! { #name }, quote! {}),
+                ErrorVariant::Struct(r#struct) => {
+                    let field_attributes = r#struct.fields.iter().map(|e| &e.attributes);
+                    let field_names = r#struct.fields.iter().map(|e| &e.name).collect::<Vec<_>>();
+                    let field_types = r#struct.fields.iter().map(|e| &e.r#type);
+                    (
+                        quote! { { #(#(#field_attributes)* pub #field_names : #field_types),* } },
+                        quote! { #name { #(ref #field_names),* } },
+                        quote! { { #(#field_names : error.#field_names),* } },
+                    )
+                }
+                ErrorVariant::SourceStruct(source_struct) => {
+                    let source_type = &source_struct.source_type;
+                    let field_attributes = source_struct.fields.iter().map(|e| &e.attributes);
+                    let field_names = source_struct
+                        .fields
+                        .iter()
+                        .map(|e| &e.name)
+                        .collect::<Vec<_>>();
+                    let field_types = source_struct.fields.iter().map(|e| &e.r#type);
+                    (
+                        quote! { { pub source: #source_type, #(#(#field_attributes)* pub #field_names : #field_types),* } },
+                        quote! { #name { ref source, #(ref #field_names),* } },
+                        quote! { { source: error.source, #(#field_names : error.#field_names),* } },
+                    )
+                }
+                ErrorVariant::SourceTuple(source_tuple) => {
+                    let source_type = &source_tuple.source_type;
+                    (
+                        quote! { (pub #source_type); },
+                        quote! { #name(ref source) },
+                        quote! { (error.0) },
+                    )
+                }
+            };
+            let right_side = display_right_side(None, variant, crate_path);
+            let debug = if redacted_field_names(variant).is_empty() {
+                quote! { #[derive(Debug)] }
+            } else {
+                let debug_body = debug_body(variant, false, crate_path);
+                token_stream.append_all(quote! {
+                    #(#cfg_attributes)*
+                    impl core::fmt::Debug for #name {
+                        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                            let #pattern = *self;
+                            #debug_body
+                        }
+                    }
+                });
+                quote! {}
+            };
+            struct_token_stream.append_all(quote! {
+                #(#cfg_attributes)*
+                #(#attributes)*
+                #debug
+                pub struct #name #definition
+
+                #(#cfg_attributes)*
+                impl core::fmt::Display for #name {
+                    #[inline]
+                    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                        let #pattern = *self;
+                        #right_side
+                    }
+                }
+            });
+            if variant.source_type().is_some() {
+                let source_field = if is_source_tuple_type(variant) {
+                    quote! { 0 }
+                } else {
+                    quote! { source }
+                };
+                let wrapped_source = wrapped_source(variant, &Vec::new(), crate_path);
+                struct_token_stream.append_all(quote! {
+                    #(#cfg_attributes)*
+                    #[allow(unused_qualifications)]
+                    impl core::error::Error for #name {
+                        fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+                            self.#source_field.source()
+                        }
+
+                        #[allow(deprecated)]
+                        fn cause(&self) -> Option<&dyn core::error::Error> {
+                            #crate_path::__private::cause(self)
+                        }
+                    }
+
+                    #(#cfg_attributes)*
+                    #[allow(unused_qualifications)]
+                    impl #crate_path::ErrorSet for #name {
+                        fn wrapped_source(&self) -> Option<#crate_path::Source<'_>> {
+                            #[allow(unused_imports)]
+                            use #crate_path::__private::{ErrorSetSource as _, ErrorTypeSource as _};
+                            let source = &self.#source_field;
+                            #wrapped_source
+                        }
+                    }
+                });
+            } else {
+                struct_token_stream.append_all(quote! {
+                    #(#cfg_attributes)*
+                    impl core::error::Error for #name {}
+
+                    #(#cfg_attributes)*
+                    impl #crate_path::ErrorSet for #name {
+                        fn wrapped_source(&self) -> Option<#crate_path::Source<'_>> {
+                            None
+                        }
+                    }
+                });
+            }
+            if !is_declared_elsewhere {
+                token_stream.extend(struct_token_stream);
+            }
+
+            for containing_node in graph {
+                let containing_enum = &containing_node.error_enum;
+                if declared_elsewhere.contains(&containing_enum.error_name) {
+                    continue;
+                }
+                let Some(containing_variant) = containing_enum
+                    .error_variants
+                    .iter()
+                    .find(|e| is_conversion_target(variant, e))
+                else {
+                    continue;
+                };
+                let containing_enum_name = &containing_enum.error_name;
+                let containing_cfg_attributes = containing_variant.cfg_attributes();
+                let (impl_generics, ty_generics) = generic_tokens(&containing_enum.generics);
+                token_stream.append_all(quote! {
+                    #(#cfg_attributes)*
+                    #(#containing_cfg_attributes)*
+                    impl #impl_generics From<#name> for #containing_enum_name #ty_generics {
+                        fn from(error: #name) -> Self {
+                            #containing_enum_name::#name #construction
+                        }
+                    }
+                });
+            }
+        }
+    }
+}
+
 fn add_code_for_node(
     error_enum_node: &ErrorEnumGraphNode,
     graph: &[ErrorEnumGraphNode],
+    declared_elsewhere: &[Ident],
     token_stream: &mut TokenStream,
 ) {
     add_enum(error_enum_node, token_stream);
     impl_error(error_enum_node, token_stream);
     impl_display(error_enum_node, token_stream);
-    impl_froms(error_enum_node, graph, token_stream);
+    let from_types = impl_froms(error_enum_node, graph, declared_elsewhere, token_stream);
+    impl_try_from_dyn(error_enum_node, &from_types, token_stream);
+    impl_default_field_builders(error_enum_node, token_stream);
+    impl_error_set(error_enum_node, token_stream);
+    impl_contains(error_enum_node, token_stream);
+    impl_retryable(error_enum_node, token_stream);
+    impl_severity(error_enum_node, token_stream);
+    impl_user_message(error_enum_node, token_stream);
 }
 
 fn add_enum(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream) {
@@ -99,13 +302,14 @@ fn add_enum(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream
                 let cfg_attributes = &r#struct.cfg_attributes;
                 let name = &r#struct.name;
                 let fields = &r#struct.fields;
+                let field_attributes = fields.iter().map(|e| &e.attributes);
                 let field_names = fields.iter().map(|e| &e.name);
                 let field_types = fields.iter().map(|e| &e.r#type);
                 error_variant_tokens.append_all(quote::quote! {
                     #(#cfg_attributes)*
                     #(#attributes)*
                     #name {
-                        #(#field_names : #field_types),*
+                        #(#(#field_attributes)* #field_names : #field_types),*
                     },
                 });
             }
@@ -114,6 +318,7 @@ fn add_enum(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream
                 let cfg_attributes = &source_struct.cfg_attributes;
                 let name = &source_struct.name;
                 let fields = &source_struct.fields;
+                let field_attributes = fields.iter().map(|e| &e.attributes);
                 let field_names = fields.iter().map(|e| &e.name);
                 let field_types = fields.iter().map(|e| &e.r#type);
                 let source_type = &source_struct.source_type;
@@ -122,7 +327,7 @@ fn add_enum(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream
                     #(#attributes)*
                     #name {
                         source: #source_type,
-                        #(#field_names : #field_types),*
+                        #(#(#field_attributes)* #field_names : #field_types),*
                     },
                 });
             }
@@ -141,10 +346,15 @@ fn add_enum(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream
     }
     let attributes = &error_enum.attributes;
     let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
-    let debug = if error_enum.disabled.debug {
-        quote! {}
-    } else {
+    let has_redacted_fields = error_variants
+        .iter()
+        .any(|e| !redacted_field_names(e).is_empty());
+    let derive_debug =
+        !error_enum.disabled.debug && !has_redacted_fields && error_enum.debug.is_none();
+    let debug = if derive_debug {
         quote! { #[derive(Debug)] }
+    } else {
+        quote! {}
     };
     token_stream.append_all(quote::quote! {
         #(#attributes)*
@@ -153,6 +363,125 @@ fn add_enum(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream
             #error_variant_tokens
         }
     });
+    if !error_enum.disabled.debug && !derive_debug {
+        impl_debug(error_enum, token_stream);
+    }
+}
+
+/// `Debug` written according to `#[debug(..)]`. Without it, like the derived one, except `#[redact]` fields are
+/// `<redacted>`.
+fn impl_debug(error_enum: &ErrorEnum, token_stream: &mut TokenStream) {
+    let enum_name = &error_enum.error_name;
+    let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+    if let Some(DebugMode::Display(_)) = error_enum.debug {
+        let mut name_branches = TokenStream::new();
+        for variant in &error_enum.error_variants {
+            let name = variant.name();
+            let cfg_attributes = variant.cfg_attributes();
+            let name_string = name.to_string();
+            name_branches.append_all(quote! {
+                #(#cfg_attributes)*
+                #enum_name::#name { .. } => #name_string,
+            });
+        }
+        token_stream.append_all(quote! {
+            impl #impl_generics core::fmt::Debug for #enum_name #ty_generics {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    let name = match *self {
+                        #name_branches
+                    };
+                    write!(f, "{}: {}", name, self)
+                }
+            }
+        });
+        return;
+    }
+    let compact = matches!(error_enum.debug, Some(DebugMode::Compact(_)));
+    let mut debug_branches = TokenStream::new();
+    for variant in &error_enum.error_variants {
+        let name = variant.name();
+        let cfg_attributes = variant.cfg_attributes();
+        let pattern = match variant {
+            ErrorVariant::Named(_) => quote! { #enum_name::#name },
+            ErrorVariant::Struct(r#struct) => {
+                let field_names = r#struct.fields.iter().map(|e| &e.name);
+                quote! { #enum_name::#name { #(ref #field_names),* } }
+            }
+            ErrorVariant::SourceStruct(source_struct) => {
+                let field_names = source_struct.fields.iter().map(|e| &e.name);
+                quote! { #enum_name::#name { ref source, #(ref #field_names),* } }
+            }
+            ErrorVariant::SourceTuple(_) => quote! { #enum_name::#name(ref source) },
+        };
+        let debug_body = debug_body(variant, compact, &error_enum.crate_path);
+        debug_branches.append_all(quote! {
+            #(#cfg_attributes)*
+            #pattern => #debug_body,
+        });
+    }
+    let generic_names = error_enum.generics.iter().map(|e| &e.ident);
+    token_stream.append_all(quote! {
+        impl #impl_generics core::fmt::Debug for #enum_name #ty_generics where #(#generic_names: core::fmt::Debug),* {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match *self {
+                    #debug_branches
+                }
+            }
+        }
+    });
+}
+
+/// Writes the `Debug` of [variant], with its fields bound by reference. If [compact], the source is left out.
+fn debug_body(variant: &ErrorVariant, compact: bool, crate_path: &syn::Path) -> TokenStream {
+    let name = variant.name().to_string();
+    let field_value = |field: &AstInlineErrorVariantField| {
+        let field_name = &field.name;
+        if field.redact {
+            quote! { &#crate_path::Redacted(#field_name) }
+        } else {
+            quote! { #field_name }
+        }
+    };
+    match variant {
+        ErrorVariant::Named(_) => quote! { f.write_str(#name) },
+        ErrorVariant::Struct(r#struct) => {
+            let field_names = r#struct.fields.iter().map(|e| e.name.to_string());
+            let field_values = r#struct.fields.iter().map(field_value);
+            quote! {
+                f.debug_struct(#name)
+                    #(.field(#field_names, #field_values))*
+                    .finish()
+            }
+        }
+        ErrorVariant::SourceStruct(source_struct) => {
+            let field_names = source_struct.fields.iter().map(|e| e.name.to_string());
+            let field_values = source_struct.fields.iter().map(field_value);
+            if compact {
+                quote! {
+                    f.debug_struct(#name)
+                        #(.field(#field_names, #field_values))*
+                        .finish_non_exhaustive()
+                }
+            } else {
+                quote! {
+                    f.debug_struct(#name)
+                        .field("source", source)
+                        #(.field(#field_names, #field_values))*
+                        .finish()
+                }
+            }
+        }
+        ErrorVariant::SourceTuple(_) => {
+            if compact {
+                let name = format!("{name}(..)");
+                quote! { f.write_str(#name) }
+            } else {
+                quote! {
+                    f.debug_tuple(#name).field(source).finish()
+                }
+            }
+        }
+    }
 }
 
 fn impl_error(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream) {
@@ -163,6 +492,7 @@ fn impl_error(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStre
     if error_enum.disabled.error {
         return;
     }
+    let crate_path = &error_enum.crate_path;
     let enum_name = &error_enum.error_name;
     let mut source_match_branches = TokenStream::new();
     let mut has_source_match_branches = false;
@@ -195,6 +525,11 @@ fn impl_error(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStre
                     _ => None,
                 }
             }
+
+            #[allow(deprecated)]
+            fn cause(&self) -> Option<&dyn core::error::Error> {
+                #crate_path::__private::cause(self)
+            }
         });
     }
     let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
@@ -223,50 +558,8 @@ fn impl_display(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenSt
     );
     let mut error_variant_tokens = TokenStream::new();
     for variant in error_variants {
-        let right_side: TokenStream;
         let name = &variant.name();
-        if let Some(display) = &variant.display() {
-            let tokens = &display.tokens;
-            // e.g. `opaque`
-            if is_opaque(tokens.clone()) {
-                right_side = quote::quote! {
-                    write!(f, "{}", concat!(stringify!(#enum_name), "::", stringify!(#name)))
-                };
-            } else if let Some(string) = extract_string_if_str_literal(tokens.clone()) {
-                // e.g. `"{}"`
-                if is_format_str(&string) {
-                    if is_source_tuple_type(variant) {
-                        right_side = quote::quote! {
-                            write!(f, #tokens, source)
-                        };
-                    } else {
-                        right_side = quote::quote! {
-                            write!(f, #tokens)
-                        };
-                    }
-                } else {
-                    // e.g. `"literal str"`
-                    right_side = quote::quote! {
-                        write!(f, "{}", #tokens)
-                    };
-                }
-            } else {
-                // e.g. `"field: {}", source.field`
-                right_side = quote::quote! {
-                    write!(f, #tokens)
-                };
-            }
-        } else {
-            if is_source_tuple_type(variant) {
-                right_side = quote::quote! {
-                    write!(f, "{}", source)
-                };
-            } else {
-                right_side = quote::quote! {
-                    write!(f, "{}", concat!(stringify!(#enum_name), "::", stringify!(#name)))
-                };
-            }
-        }
+        let right_side = display_right_side(Some(error_enum), variant, &error_enum.crate_path);
 
         match variant {
             ErrorVariant::Named(named) => {
@@ -314,15 +607,225 @@ fn impl_display(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenSt
     });
 }
 
+/// The expression that writes the display of [variant], with its fields bound by reference. [error_enum] decides the
+/// default display and the template. Without it, e.g. for a standalone struct, the variant displays on its own.
+fn display_right_side(
+    error_enum: Option<&ErrorEnum>,
+    variant: &ErrorVariant,
+    crate_path: &syn::Path,
+) -> TokenStream {
+    let format_args = redact_format_args(
+        display_format_args(error_enum, variant),
+        variant,
+        crate_path,
+    );
+    let display_template = error_enum.and_then(|_| variant.display_template());
+    match display_template {
+        Some(display_template) => {
+            let template = &display_template.template;
+            // Only the placeholders used are passed, since unused named arguments do not compile
+            let placeholders = format_placeholders(&template.value()).unwrap_or_default();
+            let is_used = |name: &str| placeholders.contains(&Placeholder::Named(name.to_string()));
+            let message =
+                is_used("message").then(|| quote! { message = format_args!(#format_args), });
+            let name = variant.name().to_string();
+            let variant = is_used("variant").then(|| quote! { variant = #name, });
+            quote! {
+                write!(f, #template, #message #variant)
+            }
+        }
+        None => quote! {
+            write!(f, #format_args)
+        },
+    }
+}
+
+/// Wraps the `#[redact]` fields of [variant] that are arguments of [format_args] in `Redacted` - e.g. `"{}", email`,
+/// `"{e}", e = email` and the `{email}` placeholder. Fields used in expressions, like `email.len()`, are left as is.
+fn redact_format_args(
+    format_args: TokenStream,
+    variant: &ErrorVariant,
+    crate_path: &syn::Path,
+) -> TokenStream {
+    use proc_macro2::{Spacing, TokenTree};
+
+    let redacted_field_names = redacted_field_names(variant);
+    if redacted_field_names.is_empty() {
+        return format_args;
+    }
+    let is_redacted = |token: &TokenTree| matches!(token, TokenTree::Ident(ident) if redacted_field_names.contains(&ident));
+    let mut args: Vec<Vec<TokenTree>> = vec![Vec::new()];
+    for token in format_args {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => args.push(Vec::new()),
+            token => args.last_mut().unwrap().push(token),
+        }
+    }
+    let mut named_args = HashSet::new();
+    let mut redacted_args = Vec::new();
+    for arg in args.iter().skip(1).filter(|e| !e.is_empty()) {
+        let redacted_arg = match arg.as_slice() {
+            [value] if is_redacted(value) => quote! { #crate_path::Redacted(#value) },
+            [TokenTree::Ident(name), TokenTree::Punct(eq), value]
+                if eq.as_char() == '=' && eq.spacing() == Spacing::Alone =>
+            {
+                named_args.insert(name.to_string());
+                if is_redacted(value) {
+                    quote! { #name = #crate_path::Redacted(#value) }
+                } else {
+                    quote! { #(#arg)* }
+                }
+            }
+            [TokenTree::Ident(name), TokenTree::Punct(eq), ..]
+                if eq.as_char() == '=' && eq.spacing() == Spacing::Alone =>
+            {
+                named_args.insert(name.to_string());
+                quote! { #(#arg)* }
+            }
+            _ => quote! { #(#arg)* },
+        };
+        redacted_args.push(redacted_arg);
+    }
+    // Placeholders without an argument capture the binding of the same name
+    let format = &args[0];
+    if let [TokenTree::Literal(literal)] = format.as_slice() {
+        if let Ok(Lit::Str(format_str)) = syn::parse2::<Lit>(literal.to_token_stream()) {
+            for placeholder in format_placeholders(&format_str.value()).unwrap_or_default() {
+                let Placeholder::Named(name) = placeholder else {
+                    continue;
+                };
+                let Some(field_name) = redacted_field_names.iter().find(|e| e.to_string() == name)
+                else {
+                    continue;
+                };
+                if named_args.insert(name) {
+                    redacted_args.push(quote! { #field_name = #crate_path::Redacted(#field_name) });
+                }
+            }
+        }
+    }
+    quote! {
+        #(#format)*, #(#redacted_args),*
+    }
+}
+
+fn redacted_field_names(variant: &ErrorVariant) -> Vec<&Ident> {
+    variant
+        .fields()
+        .into_iter()
+        .flatten()
+        .filter(|e| e.redact)
+        .map(|e| &e.name)
+        .collect()
+}
+
+fn display_format_args(error_enum: Option<&ErrorEnum>, variant: &ErrorVariant) -> TokenStream {
+    let name = &variant.name();
+    let type_name = match error_enum {
+        Some(error_enum) => {
+            let enum_name = &error_enum.error_name;
+            quote::quote! { concat!(stringify!(#enum_name), "::", stringify!(#name)) }
+        }
+        None => quote::quote! { stringify!(#name) },
+    };
+    if let Some(display) = &variant.display() {
+        let tokens = &display.tokens;
+        // e.g. `opaque`
+        if is_opaque(tokens.clone()) {
+            quote::quote! {
+                "{}", #type_name
+            }
+        } else if let Some(string) = extract_string_if_str_literal(tokens.clone()) {
+            // e.g. `"{}"`
+            if is_format_str(&string) {
+                if is_source_tuple_type(variant) {
+                    quote::quote! {
+                        #tokens, source
+                    }
+                } else {
+                    quote::quote! {
+                        #tokens
+                    }
+                }
+            } else {
+                // e.g. `"literal str"`
+                quote::quote! {
+                    "{}", #tokens
+                }
+            }
+        } else {
+            // e.g. `"field: {}", source.field`
+            quote::quote! {
+                #tokens
+            }
+        }
+    } else {
+        if is_source_tuple_type(variant) {
+            quote::quote! {
+                "{}", source
+            }
+        } else if error_enum.is_some_and(|e| e.auto_display) || cfg!(feature = "auto_display") {
+            auto_display_format_args(variant)
+        } else {
+            quote::quote! {
+                "{}", #type_name
+            }
+        }
+    }
+}
+
+/// The display of `#[display(auto)]` - the variant name in words, followed by the fields if any. e.g.
+/// `MissingField { field }` -> "missing field (field = "author")". Fields are formatted with `Debug`, which every field
+/// already implements for the derived `Debug` of the set, while `Display` may not be.
+fn auto_display_format_args(variant: &ErrorVariant) -> TokenStream {
+    let words = words_of(&variant.name().to_string());
+    let fields = variant.fields().map(|e| e.as_slice()).unwrap_or_default();
+    if fields.is_empty() {
+        return quote::quote! {
+            "{}", #words
+        };
+    }
+    let field_names = fields.iter().map(|e| &e.name);
+    let field_formats = fields
+        .iter()
+        .map(|e| format!("{} = {{:?}}", e.name.to_string().trim_start_matches("r#")))
+        .collect::<Vec<_>>();
+    let format = format!("{} ({})", words, field_formats.join(", "));
+    quote::quote! {
+        #format, #(#field_names),*
+    }
+}
+
+/// e.g. `MissingBookDescription` -> "missing book description", `HTTPTimeout` -> "http timeout"
+fn words_of(name: &str) -> String {
+    let chars = name.trim_start_matches("r#").chars().collect::<Vec<_>>();
+    let mut words = String::new();
+    for (i, char) in chars.iter().enumerate() {
+        if char.is_uppercase() && i != 0 {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|e| e.is_lowercase());
+            if !previous.is_uppercase() || next_is_lowercase {
+                words.push(' ');
+            }
+        }
+        words.extend(char.to_lowercase());
+    }
+    words
+}
+
+/// Returns the types `From` was implemented for, with the cfg attributes of the implementation.
 fn impl_froms(
     error_enum_node: &ErrorEnumGraphNode,
     graph: &[ErrorEnumGraphNode],
+    declared_elsewhere: &[Ident],
     token_stream: &mut TokenStream,
-) {
+) -> Vec<(Vec<Attribute>, TokenStream)> {
+    let mut from_types = Vec::new();
     let error_enum = &error_enum_node.error_enum;
+    let crate_path = &error_enum.crate_path;
     let from = &error_enum.disabled.from;
     if from.as_ref().is_some_and(|e| e.is_empty()) {
-        return;
+        return from_types;
     }
     let temp = Vec::new();
     let froms_to_disable = from.as_ref().unwrap_or(&temp);
@@ -331,11 +834,15 @@ fn impl_froms(
         .flat_map(|e| e.path.get_ident())
         .collect::<Vec<_>>();
     let error_enum_name = &error_enum.error_name;
+    let is_declared_elsewhere = declared_elsewhere.contains(error_enum_name);
 
     for (from_error_enum, variant_mappings) in error_enum_node.resolved_froms(graph) {
         if froms_to_disable_idents.contains(&&from_error_enum.error_name) {
             continue;
         }
+        if is_declared_elsewhere && declared_elsewhere.contains(&from_error_enum.error_name) {
+            continue;
+        }
         let mut all_cfg_attributes = Vec::new();
         let mut error_branch_tokens = TokenStream::new();
         let from_error_enum_name = &from_error_enum.error_name;
@@ -359,7 +866,12 @@ fn impl_froms(
                 let from = from_error_enum_variant.name();
                 let to = error_enum_variant.name();
                 assert!(
-                    is_conversion_target(from_error_enum_variant, error_enum_variant),
+                    is_mapped_conversion_target(
+                        from_error_enum_variant,
+                        error_enum,
+                        error_enum_variant,
+                        graph.iter().map(|e| &e.error_enum)
+                    ),
                     "Not a valid conversion target\n\nfrom:\n\n{from}\n\nto:\n\n{to}"
                 );
             }
@@ -434,18 +946,40 @@ fn impl_froms(
                 error_branch_tokens.append_all(arm);
             }
         }
-        // Dev Note: If from has generics and they are not the same as target's, then there is no guarantee that `impl_generics`
-        // will contain all of and the correct generics definitions that are for `from_ty_generics`. Merging may cause
-        // conflicts. This guard likely won't ever be removed since the correct mixture of generics may be
-        // impossible to determine without the user explicitly specifying. Even if this guard does not hold,
-        // an "unwanted" (but no compile error) `From` may be generated. This is an edge case and we are
-        // being optimistic, so we don't just not implement `From` for all generics. But a user can opt-out
-        // with `#[disable(From(..))]`
-        if !from_error_enum.generics.is_empty() && error_enum.generics != from_error_enum.generics {
-            continue;
-        }
         let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
-        let (from_impl_generics, from_ty_generics) = generic_tokens(&from_error_enum.generics);
+        let from_ty_generics = if let Some(generic_ref) = error_enum
+            .generic_refs
+            .iter()
+            .find(|e| e.error_name == from_error_enum.error_name)
+        {
+            // The user specified the generics with `||` - e.g. `Y<String>` in `Z<T> = X<T> || Y<String>`
+            let generic_args = &generic_ref.generic_args;
+            Some(quote! {<#(#generic_args),*>})
+        } else {
+            // Dev Note: If from has generics and they are not the same as target's, then there is no guarantee that `impl_generics`
+            // will contain all of and the correct generics definitions that are for `from_ty_generics`. Merging may cause
+            // conflicts. The correct mixture of generics may be impossible to determine without the user explicitly
+            // specifying, which is done by referencing the set with `||`. Even if this guard does not hold,
+            // an "unwanted" (but no compile error) `From` may be generated. This is an edge case and we are
+            // being optimistic, so we don't just not implement `From` for all generics. But a user can opt-out
+            // with `#[disable(From(..))]`
+            if !from_error_enum.generics.is_empty()
+                && error_enum.generics != from_error_enum.generics
+            {
+                continue;
+            }
+            generic_tokens(&from_error_enum.generics).1
+        };
+        token_stream.append_all(quote::quote! {
+            #(#all_cfg_attributes)*
+            impl #impl_generics #crate_path::SubsetOf<#error_enum_name #ty_generics> for #from_error_enum_name #from_ty_generics {}
+        });
+        if !from_error_enum.disabled.error {
+            from_types.push((
+                all_cfg_attributes.clone(),
+                quote! {#from_error_enum_name #from_ty_generics},
+            ));
+        }
         token_stream.append_all(quote::quote! {
             #(#all_cfg_attributes)*
             impl #impl_generics From<#from_error_enum_name #from_ty_generics> for #error_enum_name #ty_generics {
@@ -458,7 +992,11 @@ fn impl_froms(
         });
     }
 
-    // Do not impl `From` for source where source is the same between multiple variants
+    if is_declared_elsewhere {
+        return from_types;
+    }
+
+    // Do not impl `From` for source where source is the same between multiple variants, unless one is marked `#[from]`
     let mut source_type_to_error_variants = HashMap::new();
     let mut all_source_types = HashSet::new();
     for error_variant in error_enum.error_variants.iter() {
@@ -467,7 +1005,14 @@ fn impl_froms(
                 continue;
             }
             if all_source_types.contains(source_type) {
-                source_type_to_error_variants.remove(source_type);
+                if error_variant.is_from_source() {
+                    source_type_to_error_variants.insert(source_type, error_variant);
+                } else if !source_type_to_error_variants
+                    .get(source_type)
+                    .is_some_and(|e: &&ErrorVariant| e.is_from_source())
+                {
+                    source_type_to_error_variants.remove(source_type);
+                }
             } else {
                 all_source_types.insert(source_type);
                 source_type_to_error_variants.insert(source_type, error_variant);
@@ -478,6 +1023,15 @@ fn impl_froms(
     // Add `From`'s for all valid variants that are wrappers around source errors.
     for error_variant in source_type_to_error_variants.values() {
         let source_type = error_variant.source_type();
+        if is_source_tuple_type(error_variant)
+            || is_source_only_struct_type(error_variant)
+            || is_source_defaulted_struct_type(error_variant)
+        {
+            from_types.push((
+                error_variant.cfg_attributes().clone(),
+                source_type.to_token_stream(),
+            ));
+        }
         if is_source_tuple_type(error_variant) {
             let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
             let variant_name = &error_variant.name();
@@ -502,8 +1056,432 @@ fn impl_froms(
                     }
                 }
             });
+        } else if is_source_defaulted_struct_type(error_variant) {
+            let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+            let variant_name = &error_variant.name();
+            let cfg_attributes = &error_variant.cfg_attributes();
+            let field_names = error_variant
+                .fields()
+                .into_iter()
+                .flatten()
+                .map(|e| &e.name);
+            token_stream.append_all(quote::quote! {
+                #(#cfg_attributes)*
+                impl #impl_generics From<#source_type> for #error_enum_name #ty_generics {
+                    fn from(error: #source_type) -> Self {
+                        #error_enum_name::#variant_name {
+                            source: error,
+                            #(#field_names: core::default::Default::default()),*
+                        }
+                    }
+                }
+            });
+        }
+    }
+    from_types
+}
+
+/// Adds a `with_<field>` method for every `#[default]` field, so the defaults filled in by `From` can be replaced.
+fn impl_default_field_builders(
+    error_enum_node: &ErrorEnumGraphNode,
+    token_stream: &mut TokenStream,
+) {
+    let error_enum = &error_enum_node.error_enum;
+    let enum_name = &error_enum.error_name;
+    // field name to the field and the variants with that field, in declaration order
+    let mut default_fields: Vec<(&AstInlineErrorVariantField, Vec<&ErrorVariant>)> = Vec::new();
+    for variant in error_enum.error_variants.iter() {
+        for field in variant.fields().into_iter().flatten().filter(|e| e.default) {
+            match default_fields
+                .iter_mut()
+                .find(|(e, _)| e.name == field.name)
+            {
+                Some((_, variants)) => variants.push(variant),
+                None => default_fields.push((field, vec![variant])),
+            }
         }
     }
+    if default_fields.is_empty() {
+        return;
+    }
+    let mut methods = TokenStream::new();
+    for (field, variants) in default_fields {
+        let field_name = &field.name;
+        let field_type = &field.r#type;
+        let method_name = Ident::new(&format!("with_{}", field_name), field_name.span());
+        let doc = format!(
+            "Sets `{}` if this is a variant with that field, otherwise this is returned unchanged.",
+            field_name
+        );
+        let field_binding = Ident::new(&format!("{}_mut", field_name), field_name.span());
+        let arms = variants.iter().map(|variant| {
+            let variant_name = variant.name();
+            let cfg_attributes = variant.cfg_attributes();
+            quote::quote! {
+                #(#cfg_attributes)*
+                #enum_name::#variant_name { #field_name: #field_binding, .. } => *#field_binding = #field_name,
+            }
+        });
+        methods.append_all(quote::quote! {
+            #[doc = #doc]
+            pub fn #method_name(mut self, #field_name: #field_type) -> Self {
+                match &mut self {
+                    #(#arms)*
+                    #[allow(unreachable_patterns)]
+                    _ => {}
+                }
+                self
+            }
+        });
+    }
+    let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+    token_stream.append_all(quote::quote! {
+        impl #impl_generics #enum_name #ty_generics {
+            #methods
+        }
+    });
+}
+
+/// `Contains` for the marker of each variant name.
+fn impl_contains(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream) {
+    let error_enum = &error_enum_node.error_enum;
+    let crate_path = &error_enum.crate_path;
+    let enum_name = &error_enum.error_name;
+    let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+    for variant in &error_enum.error_variants {
+        let marker = variant_marker(crate_path, variant.name());
+        let cfg_attributes = variant.cfg_attributes();
+        token_stream.append_all(quote::quote! {
+            #(#cfg_attributes)*
+            impl #impl_generics #crate_path::Contains<#marker> for #enum_name #ty_generics {}
+        });
+    }
+}
+
+/// The marker type of the variant [name] for `Contains`, which spells out the name so no two names share one - e.g.
+/// `Variant<(Char<'I'>, Char<'o'>,)>` for `Io`.
+pub(crate) fn variant_marker(crate_path: &impl ToTokens, name: &Ident) -> TokenStream {
+    let chars = name.unraw().to_string().chars().collect::<Vec<_>>();
+    quote! { #crate_path::Variant<(#(#crate_path::Char<#chars>,)*)> }
+}
+
+/// Generates `is_retryable` and `retry_after` if any variant is `#[retryable]`.
+fn impl_retryable(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream) {
+    let error_enum = &error_enum_node.error_enum;
+    let crate_path = &error_enum.crate_path;
+    if !error_enum
+        .error_variants
+        .iter()
+        .any(|e| e.retryable().is_some())
+    {
+        return;
+    }
+    let enum_name = &error_enum.error_name;
+    let mut is_retryable_branches = TokenStream::new();
+    let mut retry_after_branches = TokenStream::new();
+    for variant in &error_enum.error_variants {
+        let Some(retryable) = variant.retryable() else {
+            continue;
+        };
+        let name = variant.name();
+        let cfg_attributes = variant.cfg_attributes();
+        // Matches every shape of variant
+        is_retryable_branches.append_all(quote! {
+            #(#cfg_attributes)*
+            #enum_name::#name { .. } => true,
+        });
+        if let Some(after_ms) = &retryable.after_ms {
+            retry_after_branches.append_all(quote! {
+                #(#cfg_attributes)*
+                #enum_name::#name { .. } => Some(core::time::Duration::from_millis(#after_ms)),
+            });
+        }
+    }
+    let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+    token_stream.append_all(quote! {
+        impl #impl_generics #enum_name #ty_generics {
+            /// If the error is declared `#[retryable]`, so the operation that caused it may succeed if run again.
+            pub fn is_retryable(&self) -> bool {
+                match self {
+                    #is_retryable_branches
+                    #[allow(unreachable_patterns)]
+                    _ => false,
+                }
+            }
+
+            /// The delay declared with `#[retryable(after_ms = ..)]`.
+            pub fn retry_after(&self) -> Option<core::time::Duration> {
+                match self {
+                    #retry_after_branches
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+        }
+
+        impl #impl_generics #crate_path::Retryable for #enum_name #ty_generics {
+            fn is_retryable(&self) -> bool {
+                #enum_name::is_retryable(self)
+            }
+
+            fn retry_after(&self) -> Option<core::time::Duration> {
+                #enum_name::retry_after(self)
+            }
+        }
+    });
+}
+
+/// Generates `severity` if any variant declares `#[severity(..)]`. Other variants are `Level::Error`.
+fn impl_severity(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream) {
+    let error_enum = &error_enum_node.error_enum;
+    let crate_path = &error_enum.crate_path;
+    if !error_enum
+        .error_variants
+        .iter()
+        .any(|e| e.severity().is_some())
+    {
+        return;
+    }
+    let enum_name = &error_enum.error_name;
+    let mut severity_branches = TokenStream::new();
+    for variant in &error_enum.error_variants {
+        let Some(severity) = variant.severity() else {
+            continue;
+        };
+        let name = variant.name();
+        let cfg_attributes = variant.cfg_attributes();
+        let level = match severity.to_string().as_str() {
+            "error" => quote! { Error },
+            "warn" => quote! { Warn },
+            "info" => quote! { Info },
+            "debug" => quote! { Debug },
+            _ => quote! { Trace },
+        };
+        severity_branches.append_all(quote! {
+            #(#cfg_attributes)*
+            #enum_name::#name { .. } => #crate_path::Level::#level,
+        });
+    }
+    let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+    token_stream.append_all(quote! {
+        impl #impl_generics #enum_name #ty_generics {
+            /// The level declared with `#[severity(..)]`, or `Level::Error` if none was declared.
+            pub fn severity(&self) -> #crate_path::Level {
+                match self {
+                    #severity_branches
+                    #[allow(unreachable_patterns)]
+                    _ => #crate_path::Level::Error,
+                }
+            }
+        }
+
+        impl #impl_generics #crate_path::Severity for #enum_name #ty_generics {
+            fn severity(&self) -> #crate_path::Level {
+                #enum_name::severity(self)
+            }
+        }
+    });
+}
+
+/// Generates `user_message` if the error enum or any variant declares `#[user_message("..")]`. A variant without one
+/// uses the default of the set that declared it, like it keeps that set's display template.
+fn impl_user_message(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream) {
+    let error_enum = &error_enum_node.error_enum;
+    let crate_path = &error_enum.crate_path;
+    if error_enum.user_message.is_none()
+        && !error_enum
+            .error_variants
+            .iter()
+            .any(|e| e.user_message().is_some() || e.default_user_message().is_some())
+    {
+        return;
+    }
+    if !cfg!(feature = "alloc") {
+        let span = error_enum
+            .user_message
+            .as_ref()
+            .or_else(|| {
+                error_enum
+                    .error_variants
+                    .iter()
+                    .find_map(|e| e.user_message().or(e.default_user_message()))
+            })
+            .map(|e| e.span())
+            .unwrap_or_else(|| error_enum.error_name.span());
+        token_stream.append_all(quote::quote_spanned! {span=>
+            compile_error!("`#[user_message(..)]` requires the `alloc` feature of `error_set`.");
+        });
+        return;
+    }
+    let enum_name = &error_enum.error_name;
+    let mut user_message_branches = TokenStream::new();
+    for variant in &error_enum.error_variants {
+        let name = variant.name();
+        let cfg_attributes = variant.cfg_attributes();
+        let Some(user_message) = variant.user_message() else {
+            // The default of the set that declared the variant is not a format string, like the default of this set
+            if let Some(default_user_message) = variant.default_user_message() {
+                if error_enum.user_message.as_ref() != Some(default_user_message) {
+                    user_message_branches.append_all(quote! {
+                        #(#cfg_attributes)*
+                        #enum_name::#name { .. } => #crate_path::__private::Cow::Borrowed(#default_user_message),
+                    });
+                }
+            }
+            continue;
+        };
+        if is_format_str(&user_message.value()) {
+            let pattern = match variant {
+                ErrorVariant::Named(_) => quote! { #enum_name::#name },
+                ErrorVariant::Struct(r#struct) => {
+                    let field_names = r#struct.fields.iter().map(|e| &e.name);
+                    quote! { #enum_name::#name { #(ref #field_names),* } }
+                }
+                ErrorVariant::SourceStruct(source_struct) => {
+                    let field_names = source_struct.fields.iter().map(|e| &e.name);
+                    quote! { #enum_name::#name { ref source, #(ref #field_names),* } }
+                }
+                ErrorVariant::SourceTuple(_) => quote! { #enum_name::#name(ref source) },
+            };
+            // `#[redact]` fields are redacted like in `Display`
+            let format_args =
+                redact_format_args(user_message.to_token_stream(), variant, crate_path);
+            user_message_branches.append_all(quote! {
+                #(#cfg_attributes)*
+                #pattern => #crate_path::__private::Cow::Owned(#crate_path::__private::format!(#format_args)),
+            });
+        } else {
+            user_message_branches.append_all(quote! {
+                #(#cfg_attributes)*
+                #enum_name::#name { .. } => #crate_path::__private::Cow::Borrowed(#user_message),
+            });
+        }
+    }
+    let default_user_message = match &error_enum.user_message {
+        Some(user_message) => user_message.to_token_stream(),
+        None => quote! { "An unexpected error occurred." },
+    };
+    let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+    token_stream.append_all(quote! {
+        impl #impl_generics #enum_name #ty_generics {
+            /// The message declared with `#[user_message("..")]`, which is safe to show to end users. Unlike
+            /// `Display`, which is for internal use, like logging.
+            #[allow(unused_variables)]
+            pub fn user_message(&self) -> #crate_path::__private::Cow<'static, str> {
+                match *self {
+                    #user_message_branches
+                    #[allow(unreachable_patterns)]
+                    _ => #crate_path::__private::Cow::Borrowed(#default_user_message),
+                }
+            }
+        }
+    });
+}
+
+/// `try_from_dyn`, which downcasts to this error enum or any type it implements `From` for. Requires `std` for `Box`.
+fn impl_try_from_dyn(
+    error_enum_node: &ErrorEnumGraphNode,
+    from_types: &[(Vec<Attribute>, TokenStream)],
+    token_stream: &mut TokenStream,
+) {
+    let error_enum = &error_enum_node.error_enum;
+    let crate_path = &error_enum.crate_path;
+    if !cfg!(feature = "std") || error_enum.disabled.error {
+        return;
+    }
+    let enum_name = &error_enum.error_name;
+    let from_types_cfg_attributes = from_types.iter().map(|(e, _)| e);
+    let from_types = from_types.iter().map(|(_, e)| e);
+    let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+    token_stream.append_all(quote::quote! {
+        impl #impl_generics #enum_name #ty_generics {
+            /// Downcasts to this error, or to an error or source this can be created from with `From`. If none match,
+            /// the original error is returned.
+            pub fn try_from_dyn(
+                error: #crate_path::__private::Box<dyn core::error::Error + Send + Sync>,
+            ) -> Result<Self, #crate_path::__private::Box<dyn core::error::Error + Send + Sync>>
+            where
+                Self: 'static,
+            {
+                let error = match error.downcast::<Self>() {
+                    Ok(error) => return Ok(*error),
+                    Err(error) => error,
+                };
+                #(
+                    #(#from_types_cfg_attributes)*
+                    let error = match error.downcast::<#from_types>() {
+                        Ok(error) => return Ok(Self::from(*error)),
+                        Err(error) => error,
+                    };
+                )*
+                Err(error)
+            }
+        }
+    });
+}
+
+/// `ErrorSet`, which exposes the wrapped source that `source()` skips for variants that display as their source.
+fn impl_error_set(error_enum_node: &ErrorEnumGraphNode, token_stream: &mut TokenStream) {
+    let error_enum = &error_enum_node.error_enum;
+    let crate_path = &error_enum.crate_path;
+    if error_enum.disabled.error {
+        return;
+    }
+    let enum_name = &error_enum.error_name;
+    let mut wrapped_source_branches = TokenStream::new();
+    for variant in &error_enum.error_variants {
+        let name = &variant.name();
+        let cfg_attributes = &variant.cfg_attributes();
+        let wrapped_source = wrapped_source(variant, &error_enum.generics, crate_path);
+        if is_source_tuple_type(variant) {
+            wrapped_source_branches.append_all(quote::quote! {
+                #(#cfg_attributes)*
+                #enum_name::#name(ref source) => #wrapped_source,
+            });
+        } else if is_source_struct_type(variant) {
+            wrapped_source_branches.append_all(quote::quote! {
+                #(#cfg_attributes)*
+                #enum_name::#name { ref source, .. } => #wrapped_source,
+            });
+        }
+    }
+    let (impl_generics, ty_generics) = generic_tokens(&error_enum.generics);
+    token_stream.append_all(quote::quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #crate_path::ErrorSet for #enum_name #ty_generics {
+            fn wrapped_source(&self) -> Option<#crate_path::Source<'_>> {
+                #[allow(unused_imports)]
+                use #crate_path::__private::{ErrorSetSource as _, ErrorTypeSource as _};
+                match self {
+                    #wrapped_source_branches
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+        }
+    });
+}
+
+/// The `ErrorSet::wrapped_source` of the variant, given the wrapped error bound to `source`. Generic sources may not be
+/// `'static`, so the chain continues from their `source()` instead.
+fn wrapped_source(
+    variant: &ErrorVariant,
+    generics: &Vec<TypeParam>,
+    crate_path: &syn::Path,
+) -> TokenStream {
+    let is_displayed = match variant.display() {
+        Some(display) => display_includes_source(display),
+        // Only source tuples display as their source by default
+        None => is_source_tuple_type(variant),
+    };
+    if variant
+        .source_type()
+        .is_some_and(|e| uses_generics(e, generics))
+    {
+        quote::quote! { source.source().map(|e| #crate_path::Source::new(e, false)) }
+    } else {
+        quote::quote! { Some((&#crate_path::__private::SourceOf(source)).source(#is_displayed)) }
+    }
 }
 //************************************************************************//
 
@@ -586,6 +1564,14 @@ pub(crate) trait Common {
     fn attributes(&self) -> &Vec<Attribute>;
     fn cfg_attributes(&self) -> &Vec<Attribute>;
     fn display(&self) -> Option<&DisplayAttribute>;
+    fn maps_to(&self) -> &Vec<MapsTo>;
+    fn is_from_source(&self) -> bool;
+    fn is_standalone(&self) -> bool;
+    fn display_template(&self) -> Option<&DisplayTemplate>;
+    fn user_message(&self) -> Option<&syn::LitStr>;
+    fn default_user_message(&self) -> Option<&syn::LitStr>;
+    fn severity(&self) -> Option<&Ident>;
+    fn retryable(&self) -> Option<&Retryable>;
     fn name(&self) -> &Ident;
     fn fields(&self) -> Option<&Vec<AstInlineErrorVariantField>>;
     fn source_type(&self) -> Option<&syn::TypePath>;
@@ -628,6 +1614,70 @@ impl Common for ErrorVariant {
             ErrorVariant::SourceTuple(e) => e.display(),
         }
     }
+    fn maps_to(&self) -> &Vec<MapsTo> {
+        match self {
+            ErrorVariant::Named(e) => e.maps_to(),
+            ErrorVariant::Struct(e) => e.maps_to(),
+            ErrorVariant::SourceStruct(e) => e.maps_to(),
+            ErrorVariant::SourceTuple(e) => e.maps_to(),
+        }
+    }
+    fn is_from_source(&self) -> bool {
+        match self {
+            ErrorVariant::Named(e) => e.is_from_source(),
+            ErrorVariant::Struct(e) => e.is_from_source(),
+            ErrorVariant::SourceStruct(e) => e.is_from_source(),
+            ErrorVariant::SourceTuple(e) => e.is_from_source(),
+        }
+    }
+    fn is_standalone(&self) -> bool {
+        match self {
+            ErrorVariant::Named(e) => e.is_standalone(),
+            ErrorVariant::Struct(e) => e.is_standalone(),
+            ErrorVariant::SourceStruct(e) => e.is_standalone(),
+            ErrorVariant::SourceTuple(e) => e.is_standalone(),
+        }
+    }
+    fn display_template(&self) -> Option<&DisplayTemplate> {
+        match self {
+            ErrorVariant::Named(e) => e.display_template(),
+            ErrorVariant::Struct(e) => e.display_template(),
+            ErrorVariant::SourceStruct(e) => e.display_template(),
+            ErrorVariant::SourceTuple(e) => e.display_template(),
+        }
+    }
+    fn user_message(&self) -> Option<&syn::LitStr> {
+        match self {
+            ErrorVariant::Named(e) => e.user_message(),
+            ErrorVariant::Struct(e) => e.user_message(),
+            ErrorVariant::SourceStruct(e) => e.user_message(),
+            ErrorVariant::SourceTuple(e) => e.user_message(),
+        }
+    }
+    fn default_user_message(&self) -> Option<&syn::LitStr> {
+        match self {
+            ErrorVariant::Named(e) => e.default_user_message(),
+            ErrorVariant::Struct(e) => e.default_user_message(),
+            ErrorVariant::SourceStruct(e) => e.default_user_message(),
+            ErrorVariant::SourceTuple(e) => e.default_user_message(),
+        }
+    }
+    fn severity(&self) -> Option<&Ident> {
+        match self {
+            ErrorVariant::Named(e) => e.severity(),
+            ErrorVariant::Struct(e) => e.severity(),
+            ErrorVariant::SourceStruct(e) => e.severity(),
+            ErrorVariant::SourceTuple(e) => e.severity(),
+        }
+    }
+    fn retryable(&self) -> Option<&Retryable> {
+        match self {
+            ErrorVariant::Named(e) => e.retryable(),
+            ErrorVariant::Struct(e) => e.retryable(),
+            ErrorVariant::SourceStruct(e) => e.retryable(),
+            ErrorVariant::SourceTuple(e) => e.retryable(),
+        }
+    }
     fn name(&self) -> &Ident {
         match self {
             ErrorVariant::Named(e) => e.name(),
@@ -659,6 +1709,14 @@ pub(crate) struct Named {
     pub(crate) attributes: Vec<Attribute>,
     pub(crate) cfg_attributes: Vec<Attribute>,
     pub(crate) display: Option<DisplayAttribute>,
+    pub(crate) maps_to: Vec<MapsTo>,
+    pub(crate) from_source: bool,
+    pub(crate) standalone: bool,
+    pub(crate) retryable: Option<Retryable>,
+    pub(crate) severity: Option<Ident>,
+    pub(crate) user_message: Option<syn::LitStr>,
+    pub(crate) display_template: Option<DisplayTemplate>,
+    pub(crate) default_user_message: Option<syn::LitStr>,
     pub(crate) name: Ident,
 }
 
@@ -672,6 +1730,30 @@ impl Common for Named {
     fn display(&self) -> Option<&DisplayAttribute> {
         self.display.as_ref()
     }
+    fn maps_to(&self) -> &Vec<MapsTo> {
+        &self.maps_to
+    }
+    fn is_from_source(&self) -> bool {
+        self.from_source
+    }
+    fn is_standalone(&self) -> bool {
+        self.standalone
+    }
+    fn display_template(&self) -> Option<&DisplayTemplate> {
+        self.display_template.as_ref()
+    }
+    fn user_message(&self) -> Option<&syn::LitStr> {
+        self.user_message.as_ref()
+    }
+    fn default_user_message(&self) -> Option<&syn::LitStr> {
+        self.default_user_message.as_ref()
+    }
+    fn severity(&self) -> Option<&Ident> {
+        self.severity.as_ref()
+    }
+    fn retryable(&self) -> Option<&Retryable> {
+        self.retryable.as_ref()
+    }
     fn name(&self) -> &Ident {
         &self.name
     }
@@ -688,6 +1770,14 @@ pub(crate) struct Struct {
     pub(crate) attributes: Vec<Attribute>,
     pub(crate) cfg_attributes: Vec<Attribute>,
     pub(crate) display: Option<DisplayAttribute>,
+    pub(crate) maps_to: Vec<MapsTo>,
+    pub(crate) from_source: bool,
+    pub(crate) standalone: bool,
+    pub(crate) retryable: Option<Retryable>,
+    pub(crate) severity: Option<Ident>,
+    pub(crate) user_message: Option<syn::LitStr>,
+    pub(crate) display_template: Option<DisplayTemplate>,
+    pub(crate) default_user_message: Option<syn::LitStr>,
     pub(crate) name: Ident,
     // Dev Note: This field will never be empty. Otherwise it should just be a [Named]
     pub(crate) fields: Vec<AstInlineErrorVariantField>,
@@ -703,6 +1793,30 @@ impl Common for Struct {
     fn display(&self) -> Option<&DisplayAttribute> {
         self.display.as_ref()
     }
+    fn maps_to(&self) -> &Vec<MapsTo> {
+        &self.maps_to
+    }
+    fn is_from_source(&self) -> bool {
+        self.from_source
+    }
+    fn is_standalone(&self) -> bool {
+        self.standalone
+    }
+    fn display_template(&self) -> Option<&DisplayTemplate> {
+        self.display_template.as_ref()
+    }
+    fn user_message(&self) -> Option<&syn::LitStr> {
+        self.user_message.as_ref()
+    }
+    fn default_user_message(&self) -> Option<&syn::LitStr> {
+        self.default_user_message.as_ref()
+    }
+    fn severity(&self) -> Option<&Ident> {
+        self.severity.as_ref()
+    }
+    fn retryable(&self) -> Option<&Retryable> {
+        self.retryable.as_ref()
+    }
     fn name(&self) -> &Ident {
         &self.name
     }
@@ -719,6 +1833,14 @@ pub(crate) struct SourceStruct {
     pub(crate) attributes: Vec<Attribute>,
     pub(crate) cfg_attributes: Vec<Attribute>,
     pub(crate) display: Option<DisplayAttribute>,
+    pub(crate) maps_to: Vec<MapsTo>,
+    pub(crate) from_source: bool,
+    pub(crate) standalone: bool,
+    pub(crate) retryable: Option<Retryable>,
+    pub(crate) severity: Option<Ident>,
+    pub(crate) user_message: Option<syn::LitStr>,
+    pub(crate) display_template: Option<DisplayTemplate>,
+    pub(crate) default_user_message: Option<syn::LitStr>,
     pub(crate) name: Ident,
     pub(crate) source_type: syn::TypePath,
     // Dev Note: This field can be empty
@@ -735,6 +1857,30 @@ impl Common for SourceStruct {
     fn display(&self) -> Option<&DisplayAttribute> {
         self.display.as_ref()
     }
+    fn maps_to(&self) -> &Vec<MapsTo> {
+        &self.maps_to
+    }
+    fn is_from_source(&self) -> bool {
+        self.from_source
+    }
+    fn is_standalone(&self) -> bool {
+        self.standalone
+    }
+    fn display_template(&self) -> Option<&DisplayTemplate> {
+        self.display_template.as_ref()
+    }
+    fn user_message(&self) -> Option<&syn::LitStr> {
+        self.user_message.as_ref()
+    }
+    fn default_user_message(&self) -> Option<&syn::LitStr> {
+        self.default_user_message.as_ref()
+    }
+    fn severity(&self) -> Option<&Ident> {
+        self.severity.as_ref()
+    }
+    fn retryable(&self) -> Option<&Retryable> {
+        self.retryable.as_ref()
+    }
     fn name(&self) -> &Ident {
         &self.name
     }
@@ -751,6 +1897,14 @@ pub(crate) struct SourceTuple {
     pub(crate) attributes: Vec<Attribute>,
     pub(crate) cfg_attributes: Vec<Attribute>,
     pub(crate) display: Option<DisplayAttribute>,
+    pub(crate) maps_to: Vec<MapsTo>,
+    pub(crate) from_source: bool,
+    pub(crate) standalone: bool,
+    pub(crate) retryable: Option<Retryable>,
+    pub(crate) severity: Option<Ident>,
+    pub(crate) user_message: Option<syn::LitStr>,
+    pub(crate) display_template: Option<DisplayTemplate>,
+    pub(crate) default_user_message: Option<syn::LitStr>,
     pub(crate) name: Ident,
     pub(crate) source_type: syn::TypePath,
 }
@@ -765,6 +1919,30 @@ impl Common for SourceTuple {
     fn display(&self) -> Option<&DisplayAttribute> {
         self.display.as_ref()
     }
+    fn maps_to(&self) -> &Vec<MapsTo> {
+        &self.maps_to
+    }
+    fn is_from_source(&self) -> bool {
+        self.from_source
+    }
+    fn is_standalone(&self) -> bool {
+        self.standalone
+    }
+    fn display_template(&self) -> Option<&DisplayTemplate> {
+        self.display_template.as_ref()
+    }
+    fn user_message(&self) -> Option<&syn::LitStr> {
+        self.user_message.as_ref()
+    }
+    fn default_user_message(&self) -> Option<&syn::LitStr> {
+        self.default_user_message.as_ref()
+    }
+    fn severity(&self) -> Option<&Ident> {
+        self.severity.as_ref()
+    }
+    fn retryable(&self) -> Option<&Retryable> {
+        self.retryable.as_ref()
+    }
     fn name(&self) -> &Ident {
         &self.name
     }
@@ -809,11 +1987,12 @@ impl ErrorEnumGraphNode {
     ) -> impl Iterator<Item = (&'a ErrorEnum, Vec<(&'a ErrorVariant, &'a ErrorVariant)>)> {
         self.froms.iter().map(|e| {
             let from = &graph[e.0];
+            let from_error_variants = self.error_enum.error_variants_of(&from.error_enum);
             let variant_mappings =
                 e.1.iter()
                     .map(|(from_index, this_index)| {
                         (
-                            &from.error_enum.error_variants[*from_index],
+                            &from_error_variants[*from_index],
                             &self.error_enum.error_variants[*this_index],
                         )
                     })
@@ -829,6 +2008,36 @@ pub(crate) struct ErrorEnum {
     pub(crate) error_name: Ident,
     pub(crate) generics: Vec<TypeParam>,
     pub(crate) disabled: Disabled,
+    /// The default `#[user_message("..")]` of variants that do not declare one
+    pub(crate) user_message: Option<syn::LitStr>,
+    /// How the generated `Debug` is written, declared with `#[debug(..)]`
+    pub(crate) debug: Option<DebugMode>,
+    /// If variants without `#[display(..)]` are displayed in words, declared with `#[display(auto)]`
+    pub(crate) auto_display: bool,
+    /// The path the generated code refers to this crate by, declared with `crate = path`
+    pub(crate) crate_path: syn::Path,
+    pub(crate) error_variants: Vec<ErrorVariant>,
+    pub(crate) generic_refs: Vec<GenericRef>,
+}
+
+impl ErrorEnum {
+    /// The variants of [other], as referenced by this error enum. If this references [other] with generics
+    /// e.g. `X<T> = Y<String>`, then the generics in the variants are replaced.
+    pub(crate) fn error_variants_of<'a>(&'a self, other: &'a ErrorEnum) -> &'a Vec<ErrorVariant> {
+        self.generic_refs
+            .iter()
+            .find(|e| e.error_name == other.error_name)
+            .map(|e| &e.error_variants)
+            .unwrap_or(&other.error_variants)
+    }
+}
+
+/// A reference to a generic error enum with the generics specified - e.g. `Y<String>` in `Z<T> = X<T> || Y<String>`
+#[derive(Clone)]
+pub(crate) struct GenericRef {
+    pub(crate) error_name: Ident,
+    pub(crate) generic_args: Vec<syn::Type>,
+    /// The variants of the referenced error enum, with the generics replaced by [generic_args]
     pub(crate) error_variants: Vec<ErrorVariant>,
 }
 
@@ -889,6 +2098,39 @@ fn is_format_str(input: &str) -> bool {
     return interpolation_candidate_found && start_count == end_count;
 }
 
+/// If the display writes the source - e.g. `"{0}"`, `"{source}"` or `"{}", source.field`
+fn display_includes_source(display: &DisplayAttribute) -> bool {
+    fn any_source_ident(tokens: TokenStream) -> bool {
+        tokens.into_iter().any(|e| match e {
+            proc_macro2::TokenTree::Ident(ident) => ident == "source",
+            proc_macro2::TokenTree::Group(group) => any_source_ident(group.stream()),
+            _ => false,
+        })
+    }
+    if is_opaque(display.tokens.clone()) {
+        return false;
+    }
+    if any_source_ident(display.tokens.clone()) {
+        return true;
+    }
+    let Some(proc_macro2::TokenTree::Literal(literal)) = display.tokens.clone().into_iter().next()
+    else {
+        return false;
+    };
+    let Ok(Lit::Str(format_str)) = syn::parse2::<Lit>(literal.into_token_stream()) else {
+        return false;
+    };
+    let Ok(placeholders) = format_placeholders(&format_str.value()) else {
+        return false;
+    };
+    // Only a format string alone is given the source as a positional argument
+    let has_args = display.tokens.clone().into_iter().count() > 1;
+    placeholders.iter().any(|e| match e {
+        Placeholder::Positional(_) => !has_args,
+        Placeholder::Named(name) => name == "source",
+    })
+}
+
 fn is_opaque(input: TokenStream) -> bool {
     if let Ok(ident) = syn::parse2::<Ident>(input) {
         ident == "opaque"
@@ -899,6 +2141,18 @@ fn is_opaque(input: TokenStream) -> bool {
 
 //************************************************************************//
 
+/// If any of [generics] appear in [r#type]
+pub(crate) fn uses_generics(r#type: &impl ToTokens, generics: &Vec<TypeParam>) -> bool {
+    fn any_ident(tokens: TokenStream, generics: &Vec<TypeParam>) -> bool {
+        tokens.into_iter().any(|e| match e {
+            proc_macro2::TokenTree::Ident(ident) => generics.iter().any(|e| e.ident == ident),
+            proc_macro2::TokenTree::Group(group) => any_ident(group.stream(), generics),
+            _ => false,
+        })
+    }
+    any_ident(r#type.to_token_stream(), generics)
+}
+
 fn generic_tokens(generics: &Vec<TypeParam>) -> (Option<TokenStream>, Option<TokenStream>) {
     if generics.is_empty() {
         return (None, None);
@@ -925,6 +2179,14 @@ pub(crate) fn is_source_only_struct_type(error_variant: &ErrorVariant) -> bool {
             .is_some_and(|e| e.is_empty());
 }
 
+/// A source struct where every field is `#[default]`, so it can be created from the source alone.
+pub(crate) fn is_source_defaulted_struct_type(error_variant: &ErrorVariant) -> bool {
+    return error_variant.source_type().is_some()
+        && error_variant
+            .fields()
+            .is_some_and(|e| !e.is_empty() && e.iter().all(|e| e.default));
+}
+
 pub(crate) fn is_source_struct_type(error_variant: &ErrorVariant) -> bool {
     return error_variant.source_type().is_some() && error_variant.fields().as_ref().is_some();
 }
@@ -977,13 +2239,66 @@ pub(crate) fn is_source_struct_type(error_variant: &ErrorVariant) -> bool {
 ///  }
 /// ```
 pub(crate) fn is_conversion_target(this: &ErrorVariant, that: &ErrorVariant) -> bool {
+    return this.name() == that.name() && is_same_shape(this, that);
+}
+
+/// Like [is_conversion_target], but if [this] declares a `#[maps_to(..)]` for [that_enum], only the declared variant
+/// is a conversion target, regardless of its name. The same applies to any other set that contains the declared
+/// variant, e.g. one aggregating the declared set with `||`, unless it also contains a variant of the name of [this].
+pub(crate) fn is_mapped_conversion_target<'a>(
+    this: &ErrorVariant,
+    that_enum: &ErrorEnum,
+    that: &ErrorVariant,
+    error_enums: impl Iterator<Item = &'a ErrorEnum> + Clone,
+) -> bool {
+    let contains_this = that_enum
+        .error_variants
+        .iter()
+        .any(|e| is_conversion_target(this, e));
+    let mut is_mapped = false;
+    for maps_to in this.maps_to() {
+        if maps_to.error_name == that_enum.error_name {
+            is_mapped = true;
+            if &maps_to.variant_name == that.name() && is_same_shape(this, that) {
+                return true;
+            }
+            continue;
+        }
+        if contains_this {
+            continue;
+        }
+        let Some(mapped) = error_enums
+            .clone()
+            .find(|e| e.error_name == maps_to.error_name)
+            .and_then(|e| {
+                e.error_variants
+                    .iter()
+                    .find(|e| e.name() == &maps_to.variant_name)
+            })
+        else {
+            continue;
+        };
+        if that_enum
+            .error_variants
+            .iter()
+            .any(|e| is_conversion_target(mapped, e))
+        {
+            is_mapped = true;
+            if is_conversion_target(mapped, that) && is_same_shape(this, that) {
+                return true;
+            }
+        }
+    }
+    return !is_mapped && is_conversion_target(this, that);
+}
+
+/// If [this] and [that] wrap the same source type and have the same fields. Names are not considered.
+pub(crate) fn is_same_shape(this: &ErrorVariant, that: &ErrorVariant) -> bool {
     return match (&this.source_type(), &that.source_type()) {
         (Some(this_source_type), Some(other_source_type)) => {
-            this_source_type.path == other_source_type.path
-                && this.name() == that.name()
-                && this.fields() == that.fields()
+            this_source_type.path == other_source_type.path && this.fields() == that.fields()
         }
-        (None, None) => this.name() == that.name() && this.fields() == that.fields(),
+        (None, None) => this.fields() == that.fields(),
         _ => false,
     };
 }
@@ -1,27 +1,223 @@
 mod ast;
+mod errors_attribute;
 mod expand;
 mod resolve;
 mod validate;
 
-use ast::AstErrorSet;
+use ast::{AstErrorDeclaration, AstErrorSet, AstErrorsAttribute};
 use expand::expand;
+use proc_macro2::{Delimiter, TokenTree};
 use resolve::resolve;
-use validate::validate;
+use validate::{validate, validate_display_formats};
 
 #[proc_macro]
 pub fn error_set(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Dev Note: If the macro is not updating when macro changes, uncomment below, rust-analyzer may be stuck and you need to restart: https://github.com/rust-lang/rust-analyzer/issues/10027
     // let token_stream: proc_macro2::TokenStream = syn::parse_str("const int: i32 = 1;").unwrap();
     // return proc_macro::TokenStream::from(token_stream);
+    let body = proc_macro2::TokenStream::from(tokens.clone());
     let error_set = syn::parse_macro_input!(tokens as AstErrorSet);
-    let error_enums = match resolve(error_set) {
+    let error_names = error_set
+        .set_items
+        .iter()
+        .map(|e| e.error_name.clone())
+        .collect::<Vec<_>>();
+    let crate_path = error_set.crate_path.clone();
+    let mut token_stream = match expand_error_set(error_set, Vec::new()) {
+        Ok(ok) => ok,
+        Err(err) => return err.into_compile_error().into(),
+    };
+    // The declarations of this invocation, for `#[errors(..)]` to resolve references to its sets with. One macro
+    // carries them, and each set names it. See `errors_attribute::missing_ref`.
+    let Some(first_name) = error_names.first() else {
+        return token_stream.into();
+    };
+    let macro_name = quote::format_ident!("__error_set_{}", first_name);
+    token_stream.extend(quote::quote! {
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #macro_name {
+            ($($callback:tt)*) => {
+                #crate_path::__errors! { { #body } $($callback)* }
+            };
+        }
+        #(
+            #[doc(hidden)]
+            #[allow(unused_imports)]
+            pub(crate) use #macro_name as #error_names;
+        )*
+    });
+    token_stream.into()
+}
+
+/// Declares the error set of a function inline, e.g. `#[errors(DownloadError || { Timeout })]`. The set is named
+/// after the function and replaces the `_` error type of the function's return type.
+#[proc_macro_attribute]
+pub fn errors(
+    attribute: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    expand_errors(Vec::new(), attribute.into(), item.into()).into()
+}
+
+/// `#[errors(..)]` with the declarations of the `error_set!` invocations of the sets it references prepended, as
+/// `{ declarations }* (attribute) item`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __errors(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut tokens = proc_macro2::TokenStream::from(tokens).into_iter();
+    let mut declarations = Vec::new();
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                declarations.push(group.stream())
+            }
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                return expand_errors(declarations, group.stream(), tokens.collect()).into();
+            }
+            _ => unreachable!("`__errors!` is only called by the macros declared by `error_set!`"),
+        }
+    }
+}
+
+/// The marker type of a variant name for `Contains`, called by `variant!` as `$crate, Name`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __variant(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut tokens = proc_macro2::TokenStream::from(tokens)
+        .into_iter()
+        .collect::<Vec<_>>();
+    let Some(TokenTree::Ident(name)) = tokens.pop() else {
+        unreachable!("`__variant!` is only called by `variant!`")
+    };
+    // The `,` after the crate path
+    tokens.pop();
+    let crate_path = tokens.into_iter().collect::<proc_macro2::TokenStream>();
+    expand::variant_marker(&crate_path, &name).into()
+}
+
+fn expand_errors(
+    declarations: Vec<proc_macro2::TokenStream>,
+    attribute: proc_macro2::TokenStream,
+    item: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let errors_attribute = match syn::parse2::<AstErrorsAttribute>(attribute.clone()) {
+        Ok(ok) => ok,
+        Err(err) => return err.into_compile_error(),
+    };
+    let mut other_set_items = Vec::new();
+    for declaration in &declarations {
+        match syn::parse2::<AstErrorSet>(declaration.clone()) {
+            Ok(error_set) => other_set_items.extend(error_set.set_items),
+            Err(err) => return err.into_compile_error(),
+        }
+    }
+    if let Some(missing_ref) = errors_attribute::missing_ref(&errors_attribute, &other_set_items) {
+        return quote::quote! {
+            #missing_ref! { #({ #declarations })* (#attribute) #item }
+        };
+    }
+    let item_fn = match syn::parse2::<syn::ItemFn>(item) {
+        Ok(ok) => ok,
+        Err(err) => return err.into_compile_error(),
+    };
+    match errors_attribute::errors(errors_attribute, item_fn) {
+        Ok((error_set, item_fn)) => match expand_error_set(error_set, other_set_items) {
+            Ok(mut token_stream) => {
+                token_stream.extend(quote::ToTokens::into_token_stream(item_fn));
+                token_stream
+            }
+            Err(err) => err.into_compile_error(),
+        },
+        Err(err) => err.into_compile_error(),
+    }
+}
+
+/// Expands [error_set]. [other_set_items] are declared by other macro invocations, so they are only used to resolve
+/// the references to them.
+fn expand_error_set(
+    error_set: AstErrorSet,
+    other_set_items: Vec<AstErrorDeclaration>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut errors = None;
+    let display_warnings = match validate_display_formats(&error_set) {
         Ok(ok) => ok,
         Err(err) => {
-            return err.into_compile_error().into();
+            combine_error(&mut errors, err);
+            proc_macro2::TokenStream::new()
+        }
+    };
+    let declared_elsewhere = other_set_items
+        .iter()
+        .map(|e| e.error_name.clone())
+        .collect::<Vec<_>>();
+    let mut set_items = other_set_items;
+    set_items.extend(error_set.set_items);
+    let error_enums = match resolve(AstErrorSet {
+        crate_path: error_set.crate_path,
+        set_items,
+    }) {
+        Ok(ok) => ok,
+        Err(err) => {
+            combine_error(&mut errors, err);
+            return Err(errors.unwrap());
         }
     };
     if let Err(err) = validate(&error_enums) {
-        return err.into_compile_error().into();
+        combine_error(&mut errors, err);
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+    let mut token_stream = expand(error_enums, &declared_elsewhere);
+    token_stream.extend(display_warnings);
+    Ok(token_stream)
+}
+
+/// Adds [error] to [errors], so every error can be reported at once instead of stopping at the first.
+pub(crate) fn combine_error(errors: &mut Option<syn::Error>, error: syn::Error) {
+    match errors {
+        Some(errors) => errors.combine(error),
+        None => *errors = Some(error),
+    }
+}
+
+/// A " Did you mean `..`?" hint for the candidate closest to [name], or empty if none are close enough.
+pub(crate) fn did_you_mean<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> String {
+    let max_distance = (name.chars().count() / 3).max(1);
+    let closest = candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| {
+            let distance = if candidate.eq_ignore_ascii_case(name) {
+                0
+            } else {
+                edit_distance(name, candidate)
+            };
+            (distance, candidate)
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance);
+    match closest {
+        Some((_, candidate)) => format!(" Did you mean `{candidate}`?"),
+        None => String::new(),
+    }
+}
+
+/// The Levenshtein distance between [a] and [b].
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous_row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j] + usize::from(a_char != *b_char);
+            row.push(substitution.min(previous_row[j + 1] + 1).min(row[j] + 1));
+        }
+        previous_row = row;
     }
-    expand(error_enums).into()
+    previous_row[b.len()]
 }
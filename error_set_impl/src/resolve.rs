@@ -1,37 +1,74 @@
 use std::collections::HashMap;
 
 use crate::ast::{
-    AstErrorDeclaration, AstErrorSet, AstErrorVariant, AstInlineErrorVariantField, Disabled,
-    RefError,
+    default_crate_path, AstErrorDeclaration, AstErrorSet, AstErrorVariant, DebugMode, Disabled,
+    DisplayTemplate, RefError, SetAssertions,
 };
-use crate::expand::{ErrorEnum, ErrorVariant, Named, SourceStruct, SourceTuple, Struct};
+use crate::expand::{
+    is_conversion_target, is_mapped_conversion_target, is_same_shape, Common, ErrorEnum,
+    ErrorVariant, GenericRef, Named, SourceStruct, SourceTuple, Struct,
+};
+use crate::{combine_error, did_you_mean};
 
 use quote::ToTokens;
+use syn::visit_mut::{self, VisitMut};
 use syn::{Attribute, Ident, TypeParam};
 
 /// Constructs [ErrorEnum]s from the ast, resolving any references to other sets. The returned result is
 /// all error sets with the full expansion.
 pub(crate) fn resolve(error_set: AstErrorSet) -> syn::Result<Vec<ErrorEnum>> {
     let mut error_enum_builders: Vec<ErrorEnumBuilder> = Vec::new();
+    // (declaring error set, declared variant) - only inline variants, not those pulled in through `||`
+    let mut inline_variants: Vec<(Ident, Ident)> = Vec::new();
+    let mut set_assertions: Vec<(Ident, SetAssertions)> = Vec::new();
 
-    for declaration in error_set.set_items.into_iter() {
+    let AstErrorSet {
+        crate_path,
+        set_items,
+    } = error_set;
+    for declaration in set_items.into_iter() {
         let AstErrorDeclaration {
             attributes,
             error_name,
             generics,
             disabled,
+            assertions,
+            user_message,
+            display_template,
+            debug,
+            auto_display,
             parts,
         } = declaration;
+        set_assertions.push((error_name.clone(), assertions));
 
-        let mut error_enum_builder =
-            ErrorEnumBuilder::new(error_name, attributes, generics, disabled);
+        let mut error_enum_builder = ErrorEnumBuilder::new(
+            error_name,
+            attributes,
+            generics,
+            disabled,
+            user_message,
+            debug,
+            auto_display,
+        );
+        error_enum_builder.display_template = display_template;
+        error_enum_builder.crate_path = crate_path.clone();
 
         for part in parts.into_iter() {
             match part {
                 crate::ast::AstInlineOrRefError::Inline(inline_part) => {
-                    error_enum_builder
-                        .error_variants
-                        .extend(inline_part.error_variants.into_iter());
+                    inline_variants.extend(
+                        inline_part
+                            .error_variants
+                            .iter()
+                            .map(|e| (error_enum_builder.error_name.clone(), e.name.clone())),
+                    );
+                    error_enum_builder.error_variants.extend(
+                        inline_part.error_variants.into_iter().map(|mut e| {
+                            e.display_template = error_enum_builder.display_template.clone();
+                            e.default_user_message = error_enum_builder.user_message.clone();
+                            e
+                        }),
+                    );
                 }
                 crate::ast::AstInlineOrRefError::Ref(ref_part) => {
                     error_enum_builder.add_ref_part(ref_part);
@@ -41,16 +78,311 @@ pub(crate) fn resolve(error_set: AstErrorSet) -> syn::Result<Vec<ErrorEnum>> {
         error_enum_builders.push(error_enum_builder);
     }
     let error_enums = resolve_builders(error_enum_builders)?;
+    let mut errors = None;
+    if let Err(error) = resolve_maps_to(&error_enums, &inline_variants) {
+        combine_error(&mut errors, error);
+    }
+    if let Err(error) = resolve_from_sources(&error_enums, &inline_variants) {
+        combine_error(&mut errors, error);
+    }
+    if let Err(error) = resolve_set_assertions(&error_enums, &set_assertions) {
+        combine_error(&mut errors, error);
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
 
     Ok(error_enums)
 }
 
+/// Checks at most one variant of each source type is marked `#[from]` in every error set. Since `#[from]` is carried
+/// through `||`, this may also happen when aggregating sets that marked different variants.
+fn resolve_from_sources(
+    error_enums: &[ErrorEnum],
+    inline_variants: &[(Ident, Ident)],
+) -> syn::Result<()> {
+    let mut errors = None;
+    for error_enum in error_enums {
+        let from = &error_enum.disabled.from;
+        if from.as_ref().is_some_and(|e| e.is_empty()) {
+            continue;
+        }
+        let froms_to_disable = from.as_deref().unwrap_or_default();
+        let mut from_sources: Vec<&ErrorVariant> = Vec::new();
+        for variant in error_enum
+            .error_variants
+            .iter()
+            .filter(|e| e.is_from_source())
+        {
+            let source_type = variant
+                .source_type()
+                .expect("`#[from]` variants have a source");
+            if froms_to_disable.contains(source_type) {
+                continue;
+            }
+            let Some(other) = from_sources
+                .iter()
+                .find(|e| e.source_type() == Some(source_type))
+            else {
+                from_sources.push(variant);
+                continue;
+            };
+            let is_declared_here = inline_variants
+                .iter()
+                .any(|(set, name)| set == &error_enum.error_name && name == variant.name());
+            let source_type = source_type.to_token_stream().to_string().replace(' ', "");
+            let message = format!(
+                "`{}` has more than one `#[from]` variant for `{}` - `{}` and `{}`. Only one may be marked, or disable this `From` with `#[disable(From({}))]`.",
+                error_enum.error_name,
+                source_type,
+                other.name(),
+                variant.name(),
+                source_type,
+            );
+            combine_error(
+                &mut errors,
+                if is_declared_here {
+                    syn::parse::Error::new_spanned(variant.name(), message)
+                } else {
+                    syn::parse::Error::new_spanned(&error_enum.error_name, message)
+                },
+            );
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
+/// Checks the `#[assert_subset_of(..)]` and `#[assert_disjoint_with(..)]` of each error set hold, naming the
+/// variants that break them.
+fn resolve_set_assertions(
+    error_enums: &[ErrorEnum],
+    set_assertions: &[(Ident, SetAssertions)],
+) -> syn::Result<()> {
+    let mut errors = None;
+    for (error_name, assertions) in set_assertions {
+        let error_enum = error_enums
+            .iter()
+            .find(|e| &e.error_name == error_name)
+            .expect("Every declaration is resolved to an error enum");
+        let asserted_sets = assertions
+            .subset_of
+            .iter()
+            .map(|e| (e, true))
+            .chain(assertions.disjoint_with.iter().map(|e| (e, false)));
+        for (other_name, is_subset) in asserted_sets {
+            let Some(other_enum) = error_enums.iter().find(|e| &e.error_name == other_name) else {
+                let error_names = error_enums
+                    .iter()
+                    .map(|e| e.error_name.to_string())
+                    .collect::<Vec<_>>();
+                combine_error(
+                    &mut errors,
+                    syn::parse::Error::new_spanned(
+                        other_name,
+                        format!(
+                            "Not a declared error set.{}",
+                            did_you_mean(
+                                &other_name.to_string(),
+                                error_names.iter().map(String::as_str)
+                            )
+                        ),
+                    ),
+                );
+                continue;
+            };
+            let is_from_disabled = other_enum
+                .disabled
+                .from
+                .as_ref()
+                .is_some_and(|e| e.is_empty() || e.iter().any(|e| e.path.is_ident(error_name)));
+            if is_subset && is_from_disabled {
+                combine_error(
+                    &mut errors,
+                    syn::parse::Error::new_spanned(
+                        other_name,
+                        format!(
+                            "`From<{}>` is not implemented for `{}`, since `{}` disables it with `#[disable(From(..))]`.",
+                            error_name, other_name, other_name
+                        ),
+                    ),
+                );
+                continue;
+            }
+            let offending_variants = if is_subset {
+                other_enum
+                    .error_variants_of(error_enum)
+                    .iter()
+                    .filter(|variant| {
+                        !other_enum.error_variants.iter().any(|e| {
+                            is_mapped_conversion_target(variant, other_enum, e, error_enums.iter())
+                        })
+                    })
+                    .map(|e| format!("`{}`", e.name()))
+                    .collect::<Vec<_>>()
+            } else {
+                error_enum
+                    .error_variants
+                    .iter()
+                    .filter(|variant| {
+                        other_enum
+                            .error_variants
+                            .iter()
+                            .any(|e| is_conversion_target(variant, e))
+                    })
+                    .map(|e| format!("`{}`", e.name()))
+                    .collect::<Vec<_>>()
+            };
+            if offending_variants.is_empty() {
+                continue;
+            }
+            let message = if is_subset {
+                format!(
+                    "`{}` is not a subset of `{}`, so `From<{}>` is not implemented for `{}`. `{}` is missing {}.",
+                    error_name,
+                    other_name,
+                    error_name,
+                    other_name,
+                    other_name,
+                    offending_variants.join(", ")
+                )
+            } else {
+                format!(
+                    "`{}` is not disjoint with `{}`, both contain {}.",
+                    error_name,
+                    other_name,
+                    offending_variants.join(", ")
+                )
+            };
+            combine_error(
+                &mut errors,
+                syn::parse::Error::new_spanned(other_name, message),
+            );
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
+/// Checks every `#[maps_to(..)]` points to an existing variant with a compatible shape. Only checked where the
+/// variant is declared, since aggregating with `||` copies the attribute.
+fn resolve_maps_to(
+    error_enums: &[ErrorEnum],
+    inline_variants: &[(Ident, Ident)],
+) -> syn::Result<()> {
+    let mut errors = None;
+    for error_enum in error_enums {
+        for variant in error_enum.error_variants.iter() {
+            let is_declared_here = inline_variants
+                .iter()
+                .any(|(set, name)| set == &error_enum.error_name && name == variant.name());
+            if !is_declared_here {
+                continue;
+            }
+            for maps_to in variant.maps_to() {
+                let Some(target_enum) = error_enums
+                    .iter()
+                    .find(|e| e.error_name == maps_to.error_name)
+                else {
+                    let error_names = error_enums
+                        .iter()
+                        .map(|e| e.error_name.to_string())
+                        .collect::<Vec<_>>();
+                    combine_error(
+                        &mut errors,
+                        syn::parse::Error::new_spanned(
+                            &maps_to.error_name,
+                            format!(
+                                "Not a declared error set.{}",
+                                did_you_mean(
+                                    &maps_to.error_name.to_string(),
+                                    error_names.iter().map(String::as_str)
+                                )
+                            ),
+                        ),
+                    );
+                    continue;
+                };
+                if target_enum.error_name == error_enum.error_name {
+                    combine_error(
+                        &mut errors,
+                        syn::parse::Error::new_spanned(
+                            &maps_to.error_name,
+                            "A variant cannot be mapped to a variant in its own error set.",
+                        ),
+                    );
+                    continue;
+                }
+                let Some(target_variant) = target_enum
+                    .error_variants
+                    .iter()
+                    .find(|e| e.name() == &maps_to.variant_name)
+                else {
+                    let variant_names = target_enum
+                        .error_variants
+                        .iter()
+                        .map(|e| e.name().to_string())
+                        .collect::<Vec<_>>();
+                    combine_error(
+                        &mut errors,
+                        syn::parse::Error::new_spanned(
+                            &maps_to.variant_name,
+                            format!(
+                                "`{}` is not a variant of `{}`.{}",
+                                maps_to.variant_name,
+                                maps_to.error_name,
+                                did_you_mean(
+                                    &maps_to.variant_name.to_string(),
+                                    variant_names.iter().map(String::as_str)
+                                )
+                            ),
+                        ),
+                    );
+                    continue;
+                };
+                if !is_same_shape(variant, target_variant) {
+                    combine_error(
+                        &mut errors,
+                        syn::parse::Error::new_spanned(
+                            &maps_to.variant_name,
+                            format!(
+                                "`{}::{}` cannot be mapped to `{}::{}`, the source types and fields must be the same.",
+                                error_enum.error_name,
+                                variant.name(),
+                                maps_to.error_name,
+                                maps_to.variant_name
+                            ),
+                        ),
+                    );
+                    continue;
+                }
+            }
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
 fn resolve_builders(mut error_enum_builders: Vec<ErrorEnumBuilder>) -> syn::Result<Vec<ErrorEnum>> {
+    let mut errors = None;
     for index in 0..error_enum_builders.len() {
         if !error_enum_builders[index].ref_parts_to_resolve.is_empty() {
-            resolve_builders_helper(index, &mut *error_enum_builders, &mut Vec::new())?;
+            if let Err(error) =
+                resolve_builders_helper(index, &mut *error_enum_builders, &mut Vec::new())
+            {
+                combine_error(&mut errors, error);
+            }
         }
     }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
     let error_enums = error_enum_builders
         .into_iter()
         .map(Into::into)
@@ -61,148 +393,207 @@ fn resolve_builders(mut error_enum_builders: Vec<ErrorEnumBuilder>) -> syn::Resu
 fn resolve_builders_helper<'a>(
     index: usize,
     error_enum_builders: &'a mut [ErrorEnumBuilder],
-    visited: &mut Vec<Ident>,
+    // (referencing error set, the reference) for each step taken to get to [index]
+    visited: &mut Vec<(Ident, Ident)>,
 ) -> syn::Result<Vec<AstErrorVariant>> {
-    //println!("visited `{}`", visited.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(" - "));
+    //println!("visited `{}`", visited.iter().map(|(e, _)| e.to_string()).collect::<Vec<_>>().join(" - "));
     let error_enum_builder = &error_enum_builders[index];
     let error_name = &error_enum_builder.error_name;
-    if visited.contains(error_name) {
-        visited.push(error_name.clone());
-        if let Some(pos) = visited.iter().position(|e| e == error_name) {
-            visited.drain(0..pos);
+    if let Some(pos) = visited.iter().position(|(e, _)| e == error_name) {
+        let cycle = &visited[pos..];
+        let cycle_path = cycle
+            .iter()
+            .map(|(e, _)| e.to_string())
+            .chain(std::iter::once(error_name.to_string()))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        let mut error =
+            syn::parse::Error::new_spanned(error_name, format!("Cycle Detected: {}", cycle_path));
+        for (referencing, reference) in cycle {
+            error.combine(syn::parse::Error::new_spanned(
+                reference,
+                format!(
+                    "`{}` references `{}` here, as part of the cycle {}",
+                    referencing, reference, cycle_path
+                ),
+            ));
         }
-        return Err(syn::parse::Error::new_spanned(
-            error_name.clone(),
-            format!(
-                "Cycle Detected: {}",
-                visited
-                    .iter()
-                    .map(|e| e.to_string())
-                    .collect::<Vec<_>>()
-                    .join("->")
-            ),
-        ));
+        return Err(error);
     }
     let ref_parts_to_resolve = error_enum_builder.ref_parts_to_resolve.clone();
     // If this enums ref parts have not been resolved, resolve them.
     if !ref_parts_to_resolve.is_empty() {
+        let mut errors = None;
         for ref_part in ref_parts_to_resolve {
-            let ref_error_enum_index = error_enum_builders
-                .iter()
-                .position(|e| e.error_name == ref_part.name);
-            let ref_error_enum_index = match ref_error_enum_index {
-                Some(e) => e,
-                None => {
-                    return Err(syn::parse::Error::new_spanned(
-                        &ref_part.name,
-                        "Not a declared error set.",
-                    ));
-                }
-            };
-            if !error_enum_builders[ref_error_enum_index]
-                .ref_parts_to_resolve
-                .is_empty()
-            {
-                visited.push(error_enum_builders[index].error_name.clone());
-                resolve_builders_helper(ref_error_enum_index, error_enum_builders, visited)?;
-                visited.pop();
+            if let Err(error) = resolve_ref_part(index, &ref_part, error_enum_builders, visited) {
+                combine_error(&mut errors, error);
             }
-            let (this_error_enum_builder, ref_error_enum_builder) =
-                indices::indices!(&mut *error_enum_builders, index, ref_error_enum_index);
-            // Let the ref declaration override the original generic declaration name to avoid collisions - `.. || X<T> ..`
-            if ref_part.generic_refs.len() != ref_error_enum_builder.generics.len() {
-                Err(syn::parse::Error::new_spanned(
-                    &ref_part.name,
-                    format!("A reference to {} was declared with {} generic param(s), but the original definition takes {}.", ref_part.name, ref_part.generic_refs.len(), ref_error_enum_builder.generics.len()),
-                ))?;
-            }
-            let mut error_variants = Vec::new();
-            let error_variants = if ref_part.generic_refs.is_empty() {
-                &ref_error_enum_builder.error_variants
-            } else {
-                fn ident_to_type(ident: Ident) -> syn::Type {
-                    let segment = syn::PathSegment {
-                        ident,
-                        arguments: syn::PathArguments::None, // No generic arguments
-                    };
-                    let path = syn::Path {
-                        leading_colon: None,
-                        segments: {
-                            let mut punctuated = syn::punctuated::Punctuated::new();
-                            punctuated.push(segment);
-                            punctuated
-                        },
-                    };
-                    let type_path = syn::TypePath { qself: None, path };
-                    syn::Type::Path(type_path)
-                }
+        }
+        // Cleared even if there are errors, so the errors are not reported again by sets that reference this one.
+        error_enum_builders[index].ref_parts_to_resolve.clear();
+        if let Some(errors) = errors {
+            return Err(errors);
+        }
+    }
+    // Now that are refs are solved and included in this error_enum_builder's error_variants, return them.
+    Ok(error_enum_builders[index].error_variants.clone())
+}
 
-                // rename the generics inside the variant fields to the new declared name - for `...= X<T> ..`, `T` in this case.
-                let mut generic_type_to_new_generic_type = HashMap::<syn::Type, syn::Type>::new();
-                let mut generic_type_to_new_generic_type_str = HashMap::<String, String>::new();
-                let mut generic_type_str_to_regex = HashMap::<String, regex::Regex>::new();
-                for (ref_part_generic, ref_error_enum_generic) in ref_part
-                    .generic_refs
-                    .iter()
-                    .zip(ref_error_enum_builder.generics.iter())
-                {
-                    let old = ref_error_enum_generic.ident.to_string();
-                    // e.g. For "X", matches "<X>", but not "<X" or "X>" or "X"
-                    let generic_identification_pattern = format!(
-                        r"(?P<before>[^\w\d]){}(?P<after>[^\w\d])",
-                        regex::escape(&old)
-                    );
-                    let re = regex::Regex::new(&generic_identification_pattern).unwrap();
-                    generic_type_str_to_regex.insert(old.clone(), re);
-                    let new = ref_part_generic.to_string();
-                    generic_type_to_new_generic_type_str.insert(old, new);
-                    generic_type_to_new_generic_type.insert(
-                        ident_to_type(ref_error_enum_generic.ident.clone()),
-                        ident_to_type(ref_part_generic.clone()),
-                    );
+/// Adds the variants of [ref_part] to the error enum at [index], resolving [ref_part] first if needed.
+fn resolve_ref_part(
+    index: usize,
+    ref_part: &RefError,
+    error_enum_builders: &mut [ErrorEnumBuilder],
+    visited: &mut Vec<(Ident, Ident)>,
+) -> syn::Result<()> {
+    let ref_error_enum_index = error_enum_builders
+        .iter()
+        .position(|e| e.error_name == ref_part.name);
+    let ref_error_enum_index = match ref_error_enum_index {
+        Some(e) => e,
+        None => {
+            let error_names = error_enum_builders
+                .iter()
+                .map(|e| e.error_name.to_string())
+                .collect::<Vec<_>>();
+            return Err(syn::parse::Error::new_spanned(
+                &ref_part.name,
+                format!(
+                    "Not a declared error set.{}",
+                    did_you_mean(
+                        &ref_part.name.to_string(),
+                        error_names.iter().map(String::as_str)
+                    )
+                ),
+            ));
+        }
+    };
+    if !error_enum_builders[ref_error_enum_index]
+        .ref_parts_to_resolve
+        .is_empty()
+    {
+        visited.push((
+            error_enum_builders[index].error_name.clone(),
+            ref_part.name.clone(),
+        ));
+        let result = resolve_builders_helper(ref_error_enum_index, error_enum_builders, visited);
+        visited.pop();
+        result?;
+    }
+    let (this_error_enum_builder, ref_error_enum_builder) =
+        indices::indices!(&mut *error_enum_builders, index, ref_error_enum_index);
+    // Let the ref declaration override the original generic declaration name to avoid collisions - `.. || X<T> ..`
+    if ref_part.generic_refs.len() != ref_error_enum_builder.generics.len() {
+        return Err(syn::parse::Error::new_spanned(
+            &ref_part.name,
+            format!("A reference to {} was declared with {} generic param(s), but the original definition takes {}.", ref_part.name, ref_part.generic_refs.len(), ref_error_enum_builder.generics.len()),
+        ));
+    }
+    let mut error_variants = Vec::new();
+    let error_variants = if ref_part.generic_refs.is_empty() {
+        &ref_error_enum_builder.error_variants
+    } else {
+        // rename the generics inside the variant fields to the new declared name - for `...= X<T> ..`, `T` in this case.
+        let old_to_new = ref_part
+            .generic_refs
+            .iter()
+            .zip(ref_error_enum_builder.generics.iter())
+            .map(|(ref_part_generic, ref_error_enum_generic)| {
+                (
+                    ref_error_enum_generic.ident.clone(),
+                    ref_part_generic.clone(),
+                )
+            })
+            .collect::<HashMap<Ident, syn::Type>>();
+        let trait_bounds = ref_error_enum_builder
+            .generics
+            .iter()
+            .filter_map(|generic| {
+                let mut trait_bounds = generic.bounds.iter().filter_map(|e| match e {
+                    syn::TypeParamBound::Trait(trait_bound) => Some(&trait_bound.path),
+                    _ => None,
+                });
+                match (trait_bounds.next(), trait_bounds.next()) {
+                    (Some(trait_bound), None) => Some((generic.ident.clone(), trait_bound.clone())),
+                    _ => None,
                 }
+            })
+            .collect::<HashMap<Ident, syn::Path>>();
+        let mut generic_replacer = GenericReplacer {
+            old_to_new: &old_to_new,
+            trait_bounds: &trait_bounds,
+        };
 
-                for error_variant in ref_error_enum_builder.error_variants.iter() {
-                    let new_fields = if let Some(fields) = &error_variant.fields {
-                        let mut new_fields = Vec::new();
-                        for field in fields.iter() {
-                            new_fields.push(replace_generics_in_fields(
-                                field,
-                                &generic_type_to_new_generic_type,
-                                &generic_type_to_new_generic_type_str,
-                                &generic_type_str_to_regex,
+        for error_variant in ref_error_enum_builder.error_variants.iter() {
+            let new_fields = error_variant.fields.clone().map(|mut fields| {
+                for field in fields.iter_mut() {
+                    generic_replacer.visit_type_mut(&mut field.r#type);
+                }
+                fields
+            });
+            let new_source_type = match &error_variant.source_type {
+                Some(source_type) => {
+                    let mut new_source_type = syn::Type::Path(source_type.clone());
+                    generic_replacer.visit_type_mut(&mut new_source_type);
+                    match new_source_type {
+                        syn::Type::Path(new_source_type) => Some(new_source_type),
+                        new_source_type => {
+                            return Err(syn::parse::Error::new_spanned(
+                                &ref_part.name,
+                                format!(
+                                    "The source of `{}::{}` is a generic, which can only be replaced with a path type, but was replaced with `{}`.",
+                                    ref_part.name,
+                                    error_variant.name,
+                                    new_source_type.to_token_stream()
+                                ),
                             ));
                         }
-                        Some(new_fields)
-                    } else {
-                        None
-                    };
-                    error_variants.push(AstErrorVariant {
-                        attributes: error_variant.attributes.clone(),
-                        cfg_attributes: error_variant.cfg_attributes.clone(),
-                        display: error_variant.display.clone(),
-                        name: error_variant.name.clone(),
-                        fields: new_fields,
-                        source_type: error_variant.source_type.clone(),
-                        backtrace_type: error_variant.backtrace_type.clone(),
-                    });
+                    }
                 }
-                &error_variants
+                None => None,
             };
-            for variant in error_variants {
-                let this_error_variants = &mut this_error_enum_builder.error_variants;
-                let is_variant_already_in_enum = this_error_variants
-                    .iter()
-                    .any(|e| does_occupy_the_same_space(e, &variant));
-                if !is_variant_already_in_enum {
-                    this_error_variants.push(variant.clone());
+            error_variants.push(AstErrorVariant {
+                attributes: error_variant.attributes.clone(),
+                cfg_attributes: error_variant.cfg_attributes.clone(),
+                display: error_variant.display.clone(),
+                maps_to: error_variant.maps_to.clone(),
+                from_source: error_variant.from_source,
+                standalone: error_variant.standalone,
+                retryable: error_variant.retryable.clone(),
+                severity: error_variant.severity.clone(),
+                user_message: error_variant.user_message.clone(),
+                display_template: error_variant.display_template.clone(),
+                default_user_message: error_variant.default_user_message.clone(),
+                name: error_variant.name.clone(),
+                fields: new_fields,
+                source_type: new_source_type,
+                backtrace_type: error_variant.backtrace_type.clone(),
+            });
+        }
+        this_error_enum_builder.generic_refs.push(GenericRef {
+            error_name: ref_part.name.clone(),
+            generic_args: ref_part.generic_refs.clone(),
+            error_variants: error_variants.iter().cloned().map(reshape).collect(),
+        });
+        &error_variants
+    };
+    for variant in error_variants {
+        let this_error_variants = &mut this_error_enum_builder.error_variants;
+        let is_variant_already_in_enum = this_error_variants
+            .iter()
+            .any(|e| does_occupy_the_same_space(e, &variant));
+        if !is_variant_already_in_enum {
+            let mut variant = variant.clone();
+            // The template of this set replaces the one of the referenced set, unless declared with `keep`
+            if let Some(display_template) = &this_error_enum_builder.display_template {
+                if !display_template.keep || variant.display_template.is_none() {
+                    variant.display_template = Some(display_template.clone());
                 }
             }
+            this_error_variants.push(variant);
         }
-        error_enum_builders[index].ref_parts_to_resolve.clear();
     }
-    // Now that are refs are solved and included in this error_enum_builder's error_variants, return them.
-    Ok(error_enum_builders[index].error_variants.clone())
+    Ok(())
 }
 
 /// If the error definitions occupy the same space. Useful since if this space is already occupied e.g. ` X = A || B`
@@ -233,7 +624,16 @@ struct ErrorEnumBuilder {
     pub error_name: Ident,
     pub generics: Vec<TypeParam>,
     pub disabled: Disabled,
+    pub user_message: Option<syn::LitStr>,
+    /// The `#[display_prefix(..)]` or `#[display_template(..)]` of this set
+    pub display_template: Option<DisplayTemplate>,
+    pub debug: Option<DebugMode>,
+    pub auto_display: bool,
+    /// The path the generated code refers to this crate by
+    pub crate_path: syn::Path,
     pub error_variants: Vec<AstErrorVariant>,
+    /// Generic error sets referenced with `||`, with their generics replaced by the declared ones
+    pub generic_refs: Vec<GenericRef>,
     /// Once this is empty, all [ref_parts] have been resolved and [error_variants] is complete.
     pub ref_parts_to_resolve: Vec<RefError>,
 }
@@ -244,13 +644,22 @@ impl ErrorEnumBuilder {
         attributes: Vec<Attribute>,
         generics: Vec<TypeParam>,
         disabled: Disabled,
+        user_message: Option<syn::LitStr>,
+        debug: Option<DebugMode>,
+        auto_display: bool,
     ) -> Self {
         Self {
             attributes,
             error_name,
             generics,
             disabled,
+            user_message,
+            display_template: None,
+            debug,
+            auto_display,
+            crate_path: default_crate_path(),
             error_variants: Vec::new(),
+            generic_refs: Vec::new(),
             ref_parts_to_resolve: Vec::new(),
         }
     }
@@ -271,11 +680,16 @@ impl From<ErrorEnumBuilder> for ErrorEnum {
             error_name: value.error_name,
             generics: value.generics,
             disabled: value.disabled,
+            user_message: value.user_message,
+            debug: value.debug,
+            auto_display: value.auto_display,
+            crate_path: value.crate_path,
             error_variants: value
                 .error_variants
                 .into_iter()
                 .map(|v| reshape(v))
                 .collect::<Vec<_>>(),
+            generic_refs: value.generic_refs,
         }
     }
 }
@@ -301,6 +715,14 @@ fn reshape(this: AstErrorVariant) -> ErrorVariant {
         attributes,
         cfg_attributes,
         display,
+        maps_to,
+        from_source,
+        standalone,
+        retryable,
+        severity,
+        user_message,
+        display_template,
+        default_user_message,
         name,
         fields,
         source_type,
@@ -313,6 +735,14 @@ fn reshape(this: AstErrorVariant) -> ErrorVariant {
                 attributes,
                 cfg_attributes,
                 display,
+                maps_to,
+                from_source,
+                standalone,
+                retryable,
+                severity,
+                user_message,
+                display_template,
+                default_user_message,
                 name,
                 source_type,
                 fields,
@@ -324,6 +754,14 @@ fn reshape(this: AstErrorVariant) -> ErrorVariant {
                 attributes,
                 cfg_attributes,
                 display,
+                maps_to,
+                from_source,
+                standalone,
+                retryable,
+                severity,
+                user_message,
+                display_template,
+                default_user_message,
                 name,
                 fields,
             });
@@ -334,6 +772,14 @@ fn reshape(this: AstErrorVariant) -> ErrorVariant {
                 attributes,
                 cfg_attributes,
                 display,
+                maps_to,
+                from_source,
+                standalone,
+                retryable,
+                severity,
+                user_message,
+                display_template,
+                default_user_message,
                 name,
                 source_type,
             });
@@ -344,6 +790,14 @@ fn reshape(this: AstErrorVariant) -> ErrorVariant {
                 attributes,
                 cfg_attributes,
                 display,
+                maps_to,
+                from_source,
+                standalone,
+                retryable,
+                severity,
+                user_message,
+                display_template,
+                default_user_message,
                 name,
             });
         }
@@ -352,41 +806,120 @@ fn reshape(this: AstErrorVariant) -> ErrorVariant {
 
 //************************************************************************//
 
-fn replace_generics_in_fields(
-    field: &AstInlineErrorVariantField,
-    old_to_new: &HashMap<syn::Type, syn::Type>,
-    old_to_new_str: &HashMap<String, String>,
-    old_to_match_regex: &HashMap<String, regex::Regex>,
-) -> AstInlineErrorVariantField {
-    if old_to_new.contains_key(&field.r#type) {
-        let new_type = old_to_new.get(&field.r#type).unwrap().clone();
-        return AstInlineErrorVariantField {
-            name: field.name.clone(),
-            r#type: new_type.clone(),
-        };
+/// Replaces generic type params in types, for `X<G> = {..}` referenced as `X<T>`, `G` becomes `T`. Only whole
+/// identifiers are replaced, so `G::Assoc` and `<G as Trait>::Assoc` are replaced, but `std::G` and `GG` are not.
+struct GenericReplacer<'a> {
+    old_to_new: &'a HashMap<Ident, syn::Type>,
+    /// Generics with exactly one trait bound, used to qualify associated types - `G::Assoc`
+    trait_bounds: &'a HashMap<Ident, syn::Path>,
+}
+
+impl VisitMut for GenericReplacer<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        if let syn::Type::Path(type_path) = ty {
+            if type_path.qself.is_none() {
+                if let Some(new) = type_path
+                    .path
+                    .get_ident()
+                    .and_then(|e| self.old_to_new.get(e))
+                {
+                    // Do not visit the replacement, otherwise swapped generics `X<A, B>` as `X<B, A>` are replaced twice.
+                    *ty = new.clone();
+                    return;
+                }
+            }
+        }
+        visit_mut::visit_type_mut(self, ty);
     }
-    // return field.clone();
-    let field_type_str = field.r#type.to_token_stream().to_string();
-    for (original_type, new_type) in old_to_new_str {
-        let regex = &old_to_match_regex[original_type];
-        let replaced = replace_part(&field_type_str, new_type, regex);
-        if field_type_str != replaced {
-            let new_type = syn::parse_str::<syn::Type>(&replaced)
-                .expect("Failed to parse replaced type back into type");
-            return AstInlineErrorVariantField {
-                name: field.name.clone(),
-                r#type: new_type.clone(),
-            };
+
+    fn visit_type_path_mut(&mut self, type_path: &mut syn::TypePath) {
+        // e.g. `G::Assoc`
+        let first_segment = type_path.path.segments.first();
+        let new = match first_segment {
+            Some(first_segment)
+                if type_path.qself.is_none()
+                    && type_path.path.leading_colon.is_none()
+                    && type_path.path.segments.len() > 1
+                    && first_segment.arguments.is_none() =>
+            {
+                self.old_to_new.get(&first_segment.ident)
+            }
+            _ => None,
+        };
+        let Some(new) = new else {
+            visit_mut::visit_type_path_mut(self, type_path);
+            return;
+        };
+        let first_segment = &type_path.path.segments[0].ident;
+        let span = first_segment.span();
+        match (new, self.trait_bounds.get(first_segment).cloned()) {
+            // `G::Assoc` to `<T as Trait>::Assoc`
+            (new, Some(mut trait_bound)) => {
+                self.visit_path_mut(&mut trait_bound);
+                let position = trait_bound.segments.len();
+                trait_bound
+                    .segments
+                    .extend(type_path.path.segments.iter().skip(1).cloned());
+                type_path.path = trait_bound;
+                type_path.qself = Some(syn::QSelf {
+                    lt_token: syn::Token![<](span),
+                    ty: Box::new(new.clone()),
+                    position,
+                    as_token: Some(syn::Token![as](span)),
+                    gt_token: syn::Token![>](span),
+                });
+            }
+            // `G::Assoc` to `T::Assoc`
+            (syn::Type::Path(new_path), None)
+                if new_path.qself.is_none() && new_path.path.get_ident().is_some() =>
+            {
+                type_path.path.segments[0].ident = new_path.path.get_ident().unwrap().clone();
+            }
+            // `G::Assoc` to `<Vec<u8>>::Assoc`
+            (new, None) => {
+                type_path.path.segments = type_path.path.segments.iter().skip(1).cloned().collect();
+                type_path.path.leading_colon = Some(syn::Token![::](span));
+                type_path.qself = Some(syn::QSelf {
+                    lt_token: syn::Token![<](span),
+                    ty: Box::new(new.clone()),
+                    position: 0,
+                    as_token: None,
+                    gt_token: syn::Token![>](span),
+                });
+            }
+        }
+        let position = type_path.qself.as_ref().map(|e| e.position).unwrap_or(0);
+        for segment in type_path.path.segments.iter_mut().skip(position) {
+            visit_mut::visit_path_segment_mut(self, segment);
         }
     }
-    return field.clone();
+
+    // Dev Note: The content of macros is not parsed, so any matching identifier is replaced - e.g. `G` in `my_type!(G)`
+    fn visit_macro_mut(&mut self, mac: &mut syn::Macro) {
+        mac.tokens = replace_generics_in_tokens(mac.tokens.clone(), self.old_to_new);
+    }
 }
 
-/// Assumes regex is `"(?P<before>[^\w\d]){}(?P<after>[^\w\d])"` as declared earlier
-fn replace_part(input: &str, replacement: &str, re: &regex::Regex) -> String {
-    re.replace_all(input, |caps: &regex::Captures| {
-        // Reconstruct the matched segment with the replacement
-        format!("{}{}{}", &caps["before"], replacement, &caps["after"])
-    })
-    .to_string()
+fn replace_generics_in_tokens(
+    tokens: proc_macro2::TokenStream,
+    old_to_new: &HashMap<Ident, syn::Type>,
+) -> proc_macro2::TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => match old_to_new.get(&ident) {
+                Some(new) => new.to_token_stream(),
+                None => proc_macro2::TokenTree::Ident(ident).into(),
+            },
+            proc_macro2::TokenTree::Group(group) => {
+                let mut new_group = proc_macro2::Group::new(
+                    group.delimiter(),
+                    replace_generics_in_tokens(group.stream(), old_to_new),
+                );
+                new_group.set_span(group.span());
+                proc_macro2::TokenTree::Group(new_group).into()
+            }
+            token => token.into(),
+        })
+        .collect()
 }
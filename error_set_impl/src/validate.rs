@@ -1,51 +1,453 @@
 use std::collections::HashSet;
 
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
 use syn::Ident;
 
-use crate::expand::{Common, ErrorEnum};
+use crate::ast::{AstErrorSet, AstErrorVariant, AstInlineErrorVariantField, AstInlineOrRefError};
+use crate::expand::{is_same_shape, uses_generics, Common, ErrorEnum, ErrorVariant};
+use crate::{combine_error, did_you_mean};
 
-/// Additional validation logic
+/// Additional validation logic. Every error found is reported, not just the first.
 pub fn validate(error_enums: &Vec<ErrorEnum>) -> Result<(), syn::Error> {
-    all_enums_have_unique_names(error_enums)?;
-    unique_variant_names_per_enum(error_enums)
+    let mut errors = None;
+    all_enums_have_unique_names(error_enums, &mut errors);
+    unique_variant_names_per_enum(error_enums, &mut errors);
+    default_fields_of_same_name_have_same_type(error_enums, &mut errors);
+    disabled_froms_exist(error_enums, &mut errors);
+    standalone_variants_are_generatable(error_enums, &mut errors);
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
 }
 
-fn all_enums_have_unique_names(error_enums: &Vec<ErrorEnum>) -> Result<(), syn::Error> {
+fn all_enums_have_unique_names(error_enums: &Vec<ErrorEnum>, errors: &mut Option<syn::Error>) {
     let mut unique_names: HashSet<&Ident> = HashSet::new();
     for error_enum in error_enums {
         if unique_names.contains(&error_enum.error_name) {
-            return Err(syn::parse::Error::new_spanned(
-                quote::quote! {error_enum},
-                &format!(
-                    "'{0}' already exists as an error enum.",
-                    error_enum.error_name
+            combine_error(
+                errors,
+                syn::parse::Error::new_spanned(
+                    &error_enum.error_name,
+                    format!(
+                        "'{0}' already exists as an error enum.",
+                        error_enum.error_name
+                    ),
                 ),
-            ));
+            );
+            continue;
         }
         unique_names.insert(&error_enum.error_name);
     }
-    Ok(())
 }
 
-fn unique_variant_names_per_enum(
-    error_enums: &Vec<ErrorEnum>,
-) -> Result<(), syn::Error> {
+fn unique_variant_names_per_enum(error_enums: &Vec<ErrorEnum>, errors: &mut Option<syn::Error>) {
     let mut unique_variant_names: HashSet<&Ident> = HashSet::new();
     for error_enum in error_enums {
         for variant in &error_enum.error_variants {
             let variant_name = &variant.name();
             if unique_variant_names.contains(variant_name) {
-                return Err(syn::parse::Error::new_spanned(
-                    quote::quote! {variant},
-                    &format!(
-                        "A variant with name '{0}' already exists in error enum '{1}'",
-                        variant_name, error_enum.error_name
+                combine_error(
+                    errors,
+                    syn::parse::Error::new_spanned(
+                        variant_name,
+                        format!(
+                            "A variant with name '{0}' already exists in error enum '{1}'",
+                            variant_name, error_enum.error_name
+                        ),
                     ),
-                ));
+                );
+                continue;
             }
             unique_variant_names.insert(variant_name);
         }
         unique_variant_names.clear();
     }
+}
+
+/// `#[default]` fields of the same name share one `with_<field>` method, so the types must be the same.
+fn default_fields_of_same_name_have_same_type(
+    error_enums: &Vec<ErrorEnum>,
+    errors: &mut Option<syn::Error>,
+) {
+    for error_enum in error_enums {
+        let mut default_fields: Vec<&AstInlineErrorVariantField> = Vec::new();
+        for variant in &error_enum.error_variants {
+            for field in variant.fields().into_iter().flatten().filter(|e| e.default) {
+                match default_fields.iter().find(|e| e.name == field.name) {
+                    Some(existing) if existing.r#type != field.r#type => {
+                        combine_error(
+                            errors,
+                            syn::parse::Error::new_spanned(
+                                &field.name,
+                                format!(
+                                    "`#[default]` field '{0}' has a different type than another `#[default]` field '{0}' in error enum '{1}'",
+                                    field.name, error_enum.error_name
+                                ),
+                            ),
+                        );
+                    }
+                    Some(_) => {}
+                    None => default_fields.push(field),
+                }
+            }
+        }
+    }
+}
+
+/// Each `#[disable(From(..))]` argument must be a declared error set or a source type of the error enum.
+fn disabled_froms_exist(error_enums: &Vec<ErrorEnum>, errors: &mut Option<syn::Error>) {
+    for error_enum in error_enums {
+        let Some(froms_to_disable) = &error_enum.disabled.from else {
+            continue;
+        };
+        let source_types = error_enum
+            .error_variants
+            .iter()
+            .filter_map(|e| e.source_type())
+            .collect::<Vec<_>>();
+        for from_to_disable in froms_to_disable {
+            let is_error_set = from_to_disable
+                .path
+                .get_ident()
+                .is_some_and(|ident| error_enums.iter().any(|e| &e.error_name == ident));
+            if is_error_set || source_types.contains(&from_to_disable) {
+                continue;
+            }
+            let candidates = error_enums
+                .iter()
+                .map(|e| e.error_name.to_string())
+                .chain(source_types.iter().map(|e| type_to_string(e)))
+                .collect::<Vec<_>>();
+            let from_to_disable_string = type_to_string(from_to_disable);
+            combine_error(
+                errors,
+                syn::parse::Error::new_spanned(
+                    from_to_disable,
+                    format!(
+                        "'{0}' is not a declared error set or a source type of error enum '{1}'.{2}",
+                        from_to_disable_string,
+                        error_enum.error_name,
+                        did_you_mean(
+                            &from_to_disable_string,
+                            candidates.iter().map(String::as_str)
+                        )
+                    ),
+                ),
+            );
+        }
+    }
+}
+
+/// A `#[standalone]` variant becomes a single struct, so it cannot use the generics of its error enum and every
+/// `#[standalone]` variant of the same name must have the same shape.
+fn standalone_variants_are_generatable(
+    error_enums: &Vec<ErrorEnum>,
+    errors: &mut Option<syn::Error>,
+) {
+    let mut standalone_variants: Vec<&ErrorVariant> = Vec::new();
+    for error_enum in error_enums {
+        for variant in error_enum
+            .error_variants
+            .iter()
+            .filter(|e| e.is_standalone())
+        {
+            let uses_enum_generics = variant
+                .source_type()
+                .is_some_and(|e| uses_generics(e, &error_enum.generics))
+                || variant
+                    .fields()
+                    .into_iter()
+                    .flatten()
+                    .any(|e| uses_generics(&e.r#type, &error_enum.generics));
+            if uses_enum_generics {
+                combine_error(
+                    errors,
+                    syn::parse::Error::new_spanned(
+                        variant.name(),
+                        format!(
+                            "Standalone variant '{0}' cannot use the generics of error enum '{1}'.",
+                            variant.name(),
+                            error_enum.error_name
+                        ),
+                    ),
+                );
+                continue;
+            }
+            match standalone_variants
+                .iter()
+                .find(|e| e.name() == variant.name())
+            {
+                Some(existing) if !is_same_shape(existing, variant) => {
+                    combine_error(
+                        errors,
+                        syn::parse::Error::new_spanned(
+                            variant.name(),
+                            format!(
+                                "Standalone variant '{0}' is declared with a different shape in error enum '{1}'.",
+                                variant.name(),
+                                error_enum.error_name
+                            ),
+                        ),
+                    );
+                }
+                Some(_) => {}
+                None => standalone_variants.push(variant),
+            }
+        }
+    }
+}
+
+fn type_to_string(r#type: &syn::TypePath) -> String {
+    r#type.to_token_stream().to_string().replace(' ', "")
+}
+
+//************************************************************************//
+
+/// Checks the placeholders of each `#[display("..")]` format string refer to something the variant has, so a typo
+/// is reported on the attribute instead of inside the generated code. Returns warnings for fields that are not used,
+/// if enabled.
+pub fn validate_display_formats(error_set: &AstErrorSet) -> Result<TokenStream, syn::Error> {
+    let mut errors = None;
+    let mut warnings = TokenStream::new();
+    let variants = error_set
+        .set_items
+        .iter()
+        .flat_map(|e| &e.parts)
+        .filter_map(|e| match e {
+            AstInlineOrRefError::Inline(inline) => Some(inline),
+            AstInlineOrRefError::Ref(_) => None,
+        })
+        .flat_map(|e| &e.error_variants);
+    for variant in variants {
+        if let Err(error) = validate_display_format(variant, &mut warnings) {
+            combine_error(&mut errors, error);
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(warnings),
+    }
+}
+
+fn validate_display_format(
+    variant: &AstErrorVariant,
+    warnings: &mut TokenStream,
+) -> Result<(), syn::Error> {
+    let Some(display) = &variant.display else {
+        return Ok(());
+    };
+    let mut tokens = display.tokens.clone().into_iter();
+    // e.g. `opaque` or an expression, these are left to the compiler
+    let Some(TokenTree::Literal(literal)) = tokens.next() else {
+        return Ok(());
+    };
+    let Ok(syn::Lit::Str(format_str)) = syn::parse2::<syn::Lit>(literal.into_token_stream()) else {
+        return Ok(());
+    };
+    // Each argument after the format string, split on top level commas
+    let mut args: Vec<Vec<TokenTree>> = Vec::new();
+    for token in tokens {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => args.push(Vec::new()),
+            _ => match args.last_mut() {
+                Some(arg) => arg.push(token),
+                None => return Ok(()),
+            },
+        }
+    }
+    args.retain(|e| !e.is_empty());
+    let has_args = !args.is_empty();
+    // e.g. `name = expr`
+    let named_args = args.iter().filter_map(|arg| match &arg[..] {
+        [TokenTree::Ident(name), TokenTree::Punct(eq), rest @ ..]
+            if eq.as_char() == '='
+                && !matches!(rest.first(), Some(TokenTree::Punct(e)) if e.as_char() == '=') =>
+        {
+            Some(name.to_string())
+        }
+        _ => None,
+    });
+    let placeholders = match format_placeholders(&format_str.value()) {
+        Ok(placeholders) => placeholders,
+        Err(message) => return Err(syn::parse::Error::new(format_str.span(), message)),
+    };
+    let is_source_tuple = variant.source_type.is_some() && variant.fields.is_none();
+    let fields = variant.fields.iter().flatten().collect::<Vec<_>>();
+    let field_name = |field: &AstInlineErrorVariantField| {
+        field.name.to_string().trim_start_matches("r#").to_string()
+    };
+    let mut candidates = fields.iter().map(|e| field_name(e)).collect::<Vec<_>>();
+    if variant.source_type.is_some() {
+        candidates.push("source".to_string());
+    }
+    candidates.extend(named_args);
+
+    let mut errors = None;
+    let mut implicit_positional_count = 0;
+    for placeholder in &placeholders {
+        match placeholder {
+            Placeholder::Named(name) => {
+                // Could be a constant captured from the surrounding scope
+                let is_constant_like = name.chars().any(|e| e.is_ascii_uppercase())
+                    && !name.chars().any(|e| e.is_ascii_lowercase());
+                if is_constant_like || candidates.contains(name) {
+                    continue;
+                }
+                let message = if candidates.is_empty() {
+                    format!(
+                        "`{}` is not a field of `{}`, it has no fields.",
+                        name, variant.name
+                    )
+                } else {
+                    format!(
+                        "`{}` is not a field of `{}`.{}",
+                        name,
+                        variant.name,
+                        did_you_mean(name, candidates.iter().map(String::as_str))
+                    )
+                };
+                combine_error(
+                    &mut errors,
+                    syn::parse::Error::new(format_str.span(), message),
+                );
+            }
+            Placeholder::Positional(index) => {
+                if has_args {
+                    continue;
+                }
+                let index = index.unwrap_or_else(|| {
+                    implicit_positional_count += 1;
+                    implicit_positional_count - 1
+                });
+                if is_source_tuple && index == 0 {
+                    continue;
+                }
+                let message = if is_source_tuple {
+                    format!(
+                        "`{}` only has one positional argument for its `#[display(..)]` - the source, as `{{}}` or `{{0}}`.",
+                        variant.name
+                    )
+                } else if fields.is_empty() {
+                    format!(
+                        "`{}` has no positional arguments for its `#[display(..)]`.",
+                        variant.name
+                    )
+                } else {
+                    format!(
+                        "`{}` has no positional arguments for its `#[display(..)]`, refer to fields by name - e.g. `{{{}}}`.",
+                        variant.name,
+                        field_name(fields[0])
+                    )
+                };
+                combine_error(
+                    &mut errors,
+                    syn::parse::Error::new(format_str.span(), message),
+                );
+            }
+        }
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    if cfg!(feature = "warn_unused_display_fields") && !has_args {
+        for field in fields {
+            let name = field_name(field);
+            if placeholders
+                .iter()
+                .any(|e| matches!(e, Placeholder::Named(e) if e == &name))
+            {
+                continue;
+            }
+            let note = format!(
+                "field `{}` of `{}` is not used in its `#[display(..)]`",
+                name, variant.name
+            );
+            let use_span = field.name.span();
+            let usage = quote::quote_spanned! {use_span=> unused_display_field};
+            warnings.extend(quote::quote! {
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    #[allow(non_upper_case_globals)]
+                    const unused_display_field: () = ();
+                    #usage
+                };
+            });
+        }
+    }
     Ok(())
 }
+
+/// A `{..}` in a format string
+#[derive(PartialEq)]
+pub(crate) enum Placeholder {
+    /// `{}` or `{0}`
+    Positional(Option<usize>),
+    /// `{name}`
+    Named(String),
+}
+
+/// The arguments referenced by a format string, including `width$` and `.precision$` arguments.
+pub(crate) fn format_placeholders(format: &str) -> Result<Vec<Placeholder>, String> {
+    let mut placeholders = Vec::new();
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '}' {
+            if chars.next_if_eq(&'}').is_none() {
+                return Err(
+                    "Invalid format string, unmatched `}` found. Use `}}` to escape it."
+                        .to_string(),
+                );
+            }
+            continue;
+        }
+        if c != '{' {
+            continue;
+        }
+        if chars.next_if_eq(&'{').is_some() {
+            continue;
+        }
+        let mut inner = String::new();
+        loop {
+            match chars.next() {
+                Some('}') => break,
+                Some(c) => inner.push(c),
+                None => {
+                    return Err(
+                        "Invalid format string, unmatched `{` found. Use `{{` to escape it."
+                            .to_string(),
+                    )
+                }
+            }
+        }
+        let (argument, spec) = inner.split_once(':').unwrap_or((&inner, ""));
+        placeholders.push(placeholder(argument.trim()));
+        // e.g. `{:>width$}` or `{:.1$}`
+        let mut spec_parts = spec.split('$').peekable();
+        while let Some(part) = spec_parts.next() {
+            if spec_parts.peek().is_none() {
+                if part.ends_with(".*") {
+                    placeholders.push(Placeholder::Positional(None));
+                }
+                break;
+            }
+            let argument_start = part
+                .rfind(|e: char| !(e.is_alphanumeric() || e == '_'))
+                .map_or(0, |e| e + 1);
+            placeholders.push(placeholder(&part[argument_start..]));
+        }
+    }
+    Ok(placeholders)
+}
+
+fn placeholder(argument: &str) -> Placeholder {
+    if argument.is_empty() {
+        Placeholder::Positional(None)
+    } else if let Ok(index) = argument.parse::<usize>() {
+        Placeholder::Positional(Some(index))
+    } else {
+        Placeholder::Named(argument.to_string())
+    }
+}